@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Must match `game::MAX_WORD_LENGTH`
+const MAX_WORD_LENGTH: usize = 8;
+const DICTIONARY_DIR: &str = "dictionaries";
+
+/// Read a list of words of the same length, one per line,
+/// panicking with the offending line if a word is invalid
+fn read_list(file: &str) -> Vec<String> {
+    let path = Path::new(DICTIONARY_DIR).join(file);
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Cannot read {}: {err}", path.display()));
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        let word = line.trim();
+        if word.is_empty() {
+            continue;
+        }
+        let length = words.first().map_or(word.len(), String::len);
+        if word.len() != length
+            || length > MAX_WORD_LENGTH
+            || !word.bytes().all(|c| c.is_ascii_lowercase())
+        {
+            panic!(
+                "{}:{}: invalid word {word}, every word must have the same number of lowercase letters, at most {MAX_WORD_LENGTH}",
+                path.display(),
+                line_number + 1
+            );
+        }
+        if seen.insert(word.to_string()) {
+            words.push(word.to_string());
+        }
+    }
+    words
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={DICTIONARY_DIR}");
+    let index_path = Path::new(DICTIONARY_DIR).join("index.txt");
+    let index = std::fs::read_to_string(&index_path).expect("Cannot read the dictionary index");
+
+    // each list file is compiled in once, however many dictionaries use it
+    let mut lists: HashMap<String, (usize, Vec<String>)> = HashMap::new();
+    let mut code = String::new();
+    let mut dictionaries = String::new();
+    let mut names = HashSet::new();
+    for line in index.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(final_file), Some(acceptable_file)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            panic!("Invalid line in {}: {line}", index_path.display());
+        };
+        let description = fields.collect::<Vec<_>>().join(" ");
+        assert!(names.insert(name), "Duplicate dictionary {name}");
+
+        for file in [final_file, acceptable_file] {
+            if !lists.contains_key(file) {
+                let words = read_list(file);
+                let id = lists.len();
+                let _ = writeln!(code, "const LIST_{id}: &[&str] = &{words:?};");
+                lists.insert(file.to_string(), (id, words));
+            }
+        }
+        let (final_id, final_words) = &lists[final_file];
+        let (acceptable_id, acceptable) = &lists[acceptable_file];
+        let acceptable_set: HashSet<&String> = acceptable.iter().collect();
+        if let Some(word) = final_words
+            .iter()
+            .find(|word| !acceptable_set.contains(word))
+        {
+            panic!("Final word {word} of dictionary {name} is not an acceptable word");
+        }
+        let _ = writeln!(
+            dictionaries,
+            "    Dictionary {{ name: {name:?}, description: {description:?}, \
+             final_words: LIST_{final_id}, acceptable: LIST_{acceptable_id} }},"
+        );
+    }
+    assert!(
+        !names.is_empty(),
+        "No dictionary in {}",
+        index_path.display()
+    );
+    let _ = writeln!(
+        code,
+        "pub const DICTIONARIES: &[Dictionary] = &[\n{dictionaries}];"
+    );
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    std::fs::write(Path::new(&out_dir).join("dictionaries.rs"), code).unwrap();
+}
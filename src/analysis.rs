@@ -0,0 +1,206 @@
+use crate::args::{Args, ReplayArgs};
+use crate::cache;
+use crate::feedback::{self, Feedback};
+use crate::game;
+use crate::matrix::FeedbackMatrix;
+use crate::messages;
+use crate::recorder::GameData;
+use crate::words::WordList;
+use std::collections::HashSet;
+
+/// How one guess of a finished game narrowed down the answer
+pub struct GuessAnalysis {
+    pub guess: String,
+    pub feedback: Feedback,
+    /// Final words that could still be the answer before and after the guess
+    pub before: usize,
+    pub after: usize,
+    /// Candidates expected to remain after the guess, over all possible answers
+    pub expected: f64,
+    /// The acceptable word leaving the fewest candidates on average
+    pub best_guess: String,
+    pub best_expected: f64,
+    /// 0 to 99, how close the guess is to the best one
+    pub skill: u32,
+    /// 0 to 99, the chance of a worse result than the one received
+    pub luck: u32,
+    /// Whether the guess agrees with the feedback of the earlier guesses
+    pub consistent: bool,
+}
+
+/// How many candidates of `word_length` letters fall in each feedback,
+/// given the feedback code of each candidate
+fn buckets(word_length: usize, codes: impl Iterator<Item = u16>) -> Vec<usize> {
+    let mut buckets = vec![0; feedback::feedbacks(word_length)];
+    for code in codes {
+        buckets[code as usize] += 1;
+    }
+    buckets
+}
+
+/// Each candidate is equally likely, and leaves the candidates of its bucket
+fn expected_remaining(buckets: &[usize], total: usize) -> f64 {
+    buckets.iter().map(|size| (size * size) as f64).sum::<f64>() / total as f64
+}
+
+/// The acceptable word leaving the fewest of the `candidates` answers on average,
+/// and that average. In difficult mode, only the candidates themselves are tried.
+pub fn best_guess(
+    matrix: &FeedbackMatrix,
+    candidates: &[usize],
+    difficult: bool,
+) -> Option<(String, f64)> {
+    let total = candidates.len().max(1);
+    let candidate_guesses: HashSet<usize> = candidates
+        .iter()
+        .filter_map(|answer| matrix.guess_index(matrix.answers().get(*answer)))
+        .collect();
+    let pool: Vec<usize> = if difficult {
+        candidate_guesses.iter().copied().collect()
+    } else {
+        (0..matrix.guesses().len()).collect()
+    };
+    pool.into_iter()
+        .map(|guess| {
+            let codes = candidates.iter().map(|answer| matrix.code(guess, *answer));
+            let expected =
+                expected_remaining(&buckets(matrix.answers().word_length(), codes), total);
+            (guess, expected, !candidate_guesses.contains(&guess))
+        })
+        // prefer a candidate among equally good guesses, as it may win at once
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.cmp(&b.2)).then(a.0.cmp(&b.0)))
+        .map(|(guess, expected, _)| (matrix.guesses().get(guess).to_string(), expected))
+}
+
+/// The best first guess only depends on the word lists, so it is cached with their hash
+pub fn best_opening(matrix: &FeedbackMatrix) -> Option<(String, f64)> {
+    let path = cache::cache_dir().map(|dir| dir.join("opening.json"));
+    if let Some(opening) = path
+        .as_ref()
+        .and_then(|path| cache::load(path, matrix.hash()))
+    {
+        return Some(opening);
+    }
+    let candidates: Vec<usize> = (0..matrix.answers().len()).collect();
+    let opening = best_guess(matrix, &candidates, false)?;
+    if let Some(path) = &path {
+        cache::store(path, matrix.hash(), &opening);
+    }
+    Some(opening)
+}
+
+/// Analyse every guess of a game against `ans`, which must all be lowercase words of the
+/// length of the answers of `matrix`
+pub fn analyse(
+    ans: &str,
+    guesses: &[String],
+    matrix: &FeedbackMatrix,
+) -> Result<Vec<GuessAnalysis>, String> {
+    let mut candidates: Vec<usize> = (0..matrix.answers().len()).collect();
+    let mut analyses = Vec::new();
+    for (idx, guess) in guesses.iter().enumerate() {
+        let feedback = game::score(ans, guess)?;
+        let consistent = guesses[..idx]
+            .iter()
+            .all(|earlier| game::score(guess, earlier) == game::score(ans, earlier));
+        let before = candidates.len();
+        let total = before.max(1);
+
+        let codes = candidates
+            .iter()
+            .map(|answer| matrix.code_of(guess, *answer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let guess_buckets = buckets(matrix.answers().word_length(), codes.into_iter());
+        let expected = expected_remaining(&guess_buckets, total);
+        let (best_guess, best_expected) = if idx == 0 {
+            best_opening(matrix)
+        } else {
+            best_guess(matrix, &candidates, false)
+        }
+        .unwrap_or((guess.clone(), expected));
+
+        candidates.retain(|answer| matrix.code_of(guess, *answer) == Ok(feedback.encode()));
+        let after = candidates.len();
+
+        // chances of landing in a bigger bucket, counting equal ones by half
+        let worse: f64 = guess_buckets
+            .iter()
+            .map(|size| match size.cmp(&after) {
+                std::cmp::Ordering::Greater => *size as f64,
+                std::cmp::Ordering::Equal => *size as f64 / 2.0,
+                std::cmp::Ordering::Less => 0.0,
+            })
+            .sum();
+        analyses.push(GuessAnalysis {
+            guess: guess.clone(),
+            feedback,
+            before,
+            after,
+            expected,
+            best_guess,
+            best_expected,
+            skill: (99.0 * best_expected / expected).round().min(99.0) as u32,
+            luck: (99.0 * worse / total as f64).round() as u32,
+            consistent,
+        });
+    }
+    Ok(analyses)
+}
+
+pub fn print(analyses: &[GuessAnalysis]) {
+    println!("{}", messages::get().analysis_columns());
+    for analysis in analyses {
+        let mark = if analysis.consistent { ' ' } else { '*' };
+        println!(
+            "{}{mark} {:>6} {:>6} {:>9.1}  {} {:>8.1} {:>6} {:>5}  {}",
+            analysis.guess.to_uppercase(),
+            analysis.before,
+            analysis.after,
+            analysis.expected,
+            analysis.best_guess.to_uppercase(),
+            analysis.best_expected,
+            analysis.skill,
+            analysis.luck,
+            analysis.feedback.emoji()
+        );
+    }
+    if analyses.iter().any(|analysis| !analysis.consistent) {
+        println!("{}", messages::get().analysis_inconsistent());
+    }
+}
+
+/// Analyse the games of the state file, or only the `--game`th one
+pub fn replay(
+    args: &Args,
+    replay: &ReplayArgs,
+    game_data: &GameData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut final_words = WordList::default();
+    let mut acceptable = WordList::default();
+    game::init_game(args, &mut final_words, &mut acceptable)?;
+    let matrix = FeedbackMatrix::new(&acceptable, &final_words);
+
+    let is_word = |word: &String| {
+        word.len() == final_words.word_length() && word.chars().all(|c| c.is_ascii_alphabetic())
+    };
+    for (idx, game) in game_data.games.iter().enumerate() {
+        // imported games may only know the feedback
+        if replay.game.is_some_and(|number| number != idx + 1) || !game.is_complete() {
+            continue;
+        }
+        // games played with words of another length cannot be scored against these lists
+        if !is_word(&game.answer) || !game.guesses.iter().all(is_word) {
+            eprintln!("{}", messages::get().replay_skipped(idx + 1));
+            continue;
+        }
+        let guesses: Vec<String> = game
+            .guesses
+            .iter()
+            .map(|guess| guess.to_lowercase())
+            .collect();
+        println!("{}", messages::get().replay_game(idx + 1, &game.answer));
+        print(&analyse(&game.answer.to_lowercase(), &guesses, &matrix)?);
+        println!();
+    }
+    Ok(())
+}
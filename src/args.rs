@@ -1,5 +1,9 @@
-use clap::Parser;
-use serde::{Deserialize, Serialize};
+use crate::export::ExportFormat;
+use crate::messages::Lang;
+use crate::rating::Difficulty;
+use crate::render::{ColorChoice, Format, Layout, RendererKind};
+use crate::theme::ThemeName;
+use clap::{Parser, Subcommand};
 
 /// A simple wordle game
 #[derive(Parser, Debug)]
@@ -11,67 +15,270 @@ pub struct Args {
         long,
         conflicts_with = "random",
         conflicts_with = "day",
-        conflicts_with = "seed"
+        conflicts_with = "seed",
+        global = true
     )]
     pub word: Option<String>,
 
     /// Use random answer, conflicts with -w
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub random: bool,
 
     /// Use difficult mode
-    #[arg(short = 'D', long)]
+    #[arg(short = 'D', long, global = true)]
     pub difficult: bool,
 
     /// Record statistical data of the game
-    #[arg(short = 't', long)]
+    #[arg(short = 't', long, global = true)]
     pub stats: bool,
 
+    /// Record when each game was played in the state file, so merging state files
+    /// tells a game played again from a copy of the same game
+    #[arg(long, global = true)]
+    pub timestamps: bool,
+
     /// Set the day of the game
-    #[arg(short, long, requires = "random")]
+    #[arg(short, long, requires = "random", global = true)]
     pub day: Option<usize>,
 
     /// Set the seed of the game
-    #[arg(short, long, requires = "random")]
+    #[arg(short, long, requires = "random", global = true)]
     pub seed: Option<u64>,
 
+    /// Analyse the skill and luck of every guess after each game
+    #[arg(long, global = true)]
+    pub analysis: bool,
+
+    /// Skip random answers already played in the state file
+    #[arg(long, requires = "random", global = true)]
+    pub no_repeat: bool,
+
+    /// Only use random answers rated with this difficulty
+    #[arg(long, value_enum, requires = "random", global = true)]
+    pub difficulty: Option<Difficulty>,
+
+    /// Use one of the built-in dictionaries, see --list-dictionaries
+    #[arg(long, global = true)]
+    pub dictionary: Option<String>,
+
     /// Specify the final words list
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub final_set: Option<String>,
 
     /// Specify the acceptable words list
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub acceptable_set: Option<String>,
 
     /// Persistent Storage
-    #[arg(short = 'S', long)]
+    #[arg(short = 'S', long, global = true)]
     pub state: Option<String>,
 
+    /// Keep backups of the state file, the one before this session being `<state>.1`
+    #[arg(long, global = true)]
+    pub backups: bool,
+
     /// Set configuration file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub config: Option<String>,
 
+    /// Host a race on the given address, e.g. 127.0.0.1:7878
+    #[arg(long, conflicts_with = "join", global = true)]
+    pub host: Option<String>,
+
+    /// Join a race hosted on the given address
+    #[arg(
+        long,
+        conflicts_with = "word",
+        conflicts_with = "random",
+        conflicts_with = "day",
+        conflicts_with = "seed",
+        global = true
+    )]
+    pub join: Option<String>,
+
+    /// Number of players to wait for before the race starts [default: 1]
+    #[arg(long, requires = "host", global = true)]
+    pub players: Option<usize>,
+
+    /// Player name shown to opponents in a race
+    #[arg(long, global = true)]
+    pub name: Option<String>,
+
+    /// Language of the messages, defaults to the locale
+    #[arg(long, value_enum, global = true)]
+    pub lang: Option<Lang>,
+
+    /// Colour theme of the board and keyboard [default: classic]
+    #[arg(long, value_enum, global = true)]
+    pub theme: Option<ThemeName>,
+
+    /// How the board is shown [default: auto]
+    #[arg(long, value_enum, global = true)]
+    pub renderer: Option<RendererKind>,
+
+    /// Whether to use colours [default: auto]
+    #[arg(long, value_enum, global = true)]
+    pub color: Option<ColorChoice>,
+
+    /// Print every guess so far, or only the last one [default: auto]
+    #[arg(long, value_enum, global = true)]
+    pub layout: Option<Layout>,
+
+    /// Print letters or G/Y/R/X codes [default: auto]
+    // not global, as the `--format` of `export` would be read here too, `play` has its own
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// Read answers and guesses from a file, tcp://host:port, or - for stdin [default: -]
+    #[arg(long, global = true)]
+    pub input: Option<String>,
+
+    /// Let an external program play, e.g. "python3 solver.py"
+    #[arg(long, conflicts_with = "host", conflicts_with = "join", global = true)]
+    pub bot: Option<String>,
+
+    /// Number of games the bot plays [default: the whole final list]
+    #[arg(long, requires = "bot", global = true)]
+    pub rounds: Option<usize>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Print the effective value of every setting and where it comes from
+    #[arg(long, global = true)]
+    pub print_config: bool,
+
+    /// List the built-in dictionaries
+    #[arg(long, global = true)]
+    pub list_dictionaries: bool,
+
     /// Open debug mode
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub debug: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Config {
-    pub word: Option<String>,
+/// Running without a command plays games, like `play`
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Play games, the same as running without a command
+    Play(PlayArgs),
+    /// Print the statistics of the games in the state file
+    Stats,
+    /// Run several strategies over the same answers and rank them
+    Tournament(TournamentArgs),
+    /// Analyse the skill and luck of every guess of the games in the state file
+    Replay(ReplayArgs),
+    /// Suggest guesses for a puzzle played elsewhere, from each guess and its feedback
+    Assist,
+    /// Let a strategy solve the given answer and print its guesses
+    Solve(SolveArgs),
+    /// Write the games in the state file as JSON, CSV or a Markdown report
+    Export(ExportArgs),
+    /// Add games shared from the web version, or from a CSV file, to the state file
+    Import(ImportArgs),
+    /// List the built-in dictionaries, or the words of one of them
+    Lists(ListsArgs),
+    /// Manage state files
+    #[command(subcommand)]
+    State(StateCommand),
+}
 
-    #[serde(default)]
-    pub random: bool,
+#[derive(Subcommand, Debug)]
+pub enum StateCommand {
+    /// Merge the games of several state files, e.g. from different machines
+    Merge(MergeArgs),
+}
 
-    #[serde(default)]
-    pub difficult: bool,
+#[derive(clap::Args, Debug)]
+pub struct MergeArgs {
+    /// The state files to merge
+    #[arg(required = true, num_args = 2..)]
+    pub files: Vec<String>,
 
-    #[serde(default)]
-    pub stats: bool,
+    /// The merged state file to write
+    #[arg(short, long)]
+    pub output: String,
+}
 
-    pub day: Option<usize>,
+#[derive(clap::Args, Debug)]
+pub struct PlayArgs {
+    /// Print letters or G/Y/R/X codes [default: auto]
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ImportArgs {
+    /// Files of pasted share text, or .csv files with guesses or feedback columns
+    #[arg(required = true)]
+    pub files: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SolveArgs {
+    /// The answer to find
+    pub answer: String,
+
+    /// A strategy: first, frequency, random or bot:<command>
+    #[arg(long, default_value = "frequency")]
+    pub strategy: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs {
+    /// Write to this file instead of the standard output
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// The format to write [default: from the extension of the output file, or json]
+    #[arg(long, value_enum)]
+    pub format: Option<ExportFormat>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ListsArgs {
+    /// Print the final words of this dictionary
+    pub dictionary: Option<String>,
+
+    /// Print the acceptable words instead of the final words
+    #[arg(long, requires = "dictionary")]
+    pub acceptable: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+    /// Only analyse the game with this number, counting from 1
+    #[arg(long)]
+    pub game: Option<usize>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct TournamentArgs {
+    /// A strategy: first, frequency, random or bot:<command>, repeat for several [default: the built-in ones]
+    #[arg(long = "strategy")]
+    pub strategies: Vec<String>,
+
+    /// Number of answers played by each strategy in each mode
+    #[arg(long, default_value_t = 100)]
+    pub rounds: usize,
+
+    /// Seed shuffling the final list into the answers [default: the game seed]
+    #[arg(long)]
     pub seed: Option<u64>,
-    pub final_set: Option<String>,
-    pub acceptable_set: Option<String>,
-    pub state: Option<String>,
+
+    /// Write every game, the leaderboard and the words each pair of strategies split on
+    /// to a JSON file, or the games to a .csv file
+    #[arg(long)]
+    pub export: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_global_options_fit_every_command() {
+        Args::command().debug_assert();
+    }
 }
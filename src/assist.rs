@@ -0,0 +1,90 @@
+use crate::analysis;
+use crate::args::Args;
+use crate::feedback::Feedback;
+use crate::game;
+use crate::input::Input;
+use crate::matrix::FeedbackMatrix;
+use crate::messages;
+use crate::words::WordList;
+
+/// Candidates are listed once there are at most this many
+const MAX_LISTED: usize = 10;
+
+/// Parse a row such as `crane GYXXG` or `crane 🟩🟨⬛⬛🟩` of a word of `word_length` letters
+fn parse_row(line: &str, word_length: usize) -> Option<(String, Feedback)> {
+    let mut parts = line.split_whitespace();
+    let guess = parts.next()?.to_lowercase();
+    let feedback: Feedback = parts.next()?.parse().ok()?;
+    if parts.next().is_some()
+        || guess.len() != word_length
+        || feedback.status().len() != word_length
+        || !guess.chars().all(|letter| letter.is_ascii_lowercase())
+    {
+        return None;
+    }
+    Some((guess, feedback))
+}
+
+fn suggest(args: &Args, first: bool, candidates: &[usize], matrix: &FeedbackMatrix) {
+    let messages = messages::get();
+    if candidates.len() <= MAX_LISTED {
+        let listed: Vec<String> = candidates
+            .iter()
+            .map(|answer| matrix.answers().get(*answer).to_uppercase())
+            .collect();
+        println!(
+            "{}",
+            messages.assist_candidates(candidates.len(), Some(&listed.join(", ")))
+        );
+    } else {
+        println!("{}", messages.assist_candidates(candidates.len(), None));
+    }
+    // in difficult mode, only a possible answer is sure to satisfy every hint
+    let best = if first && !args.difficult {
+        analysis::best_opening(matrix)
+    } else {
+        analysis::best_guess(matrix, candidates, args.difficult)
+    };
+    if let Some((guess, expected)) = best {
+        println!("{}", messages.assist_suggestion(&guess, expected));
+    }
+}
+
+/// Help with a puzzle played elsewhere: read each guess and the feedback it got,
+/// then narrow down the candidates and suggest the next guess
+pub fn run(args: &Args, input: &mut dyn Input) -> Result<(), Box<dyn std::error::Error>> {
+    let mut final_words = WordList::default();
+    let mut acceptable = WordList::default();
+    game::init_game(args, &mut final_words, &mut acceptable)?;
+    let matrix = FeedbackMatrix::new(&acceptable, &final_words);
+    let mut candidates: Vec<usize> = (0..matrix.answers().len()).collect();
+
+    let messages = messages::get();
+    println!("{}", messages.assist_intro());
+    suggest(args, true, &candidates, &matrix);
+    while let Some(line) = input.read_line()? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((guess, feedback)) = parse_row(&line, matrix.answers().word_length()) else {
+            println!("{}", messages.assist_invalid_row());
+            continue;
+        };
+        if feedback.is_win() {
+            println!("{}", messages.assist_solved(&guess));
+            break;
+        }
+        let remaining: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|answer| matrix.code_of(&guess, *answer) == Ok(feedback.encode()))
+            .collect();
+        if remaining.is_empty() {
+            println!("{}", messages.assist_no_match());
+            continue;
+        }
+        candidates = remaining;
+        suggest(args, false, &candidates, &matrix);
+    }
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use crate::args::Args;
+use crate::feedback::Feedback;
+use crate::game::{self, GuessResult, Outcome};
+use crate::input::Input;
+use crate::recorder::{GameData, GameRecorder};
+use crate::render::Renderer;
+use crate::solver::Solver;
+use crate::words::WordList;
+use std::cell::RefCell;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::rc::Rc;
+
+/// The bot's stdin, shared between its input and the feedback after each guess
+struct BotLink {
+    stdin: ChildStdin,
+    /// Whether the last guess read from the bot got its feedback
+    answered: bool,
+}
+
+impl BotLink {
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{line}")?;
+        self.stdin.flush()
+    }
+}
+
+/// Guesses read from the bot's stdout
+struct BotInput {
+    stdout: BufReader<ChildStdout>,
+    link: Rc<RefCell<BotLink>>,
+}
+
+impl Input for BotInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        {
+            let mut link = self.link.borrow_mut();
+            // asked again without feedback, so the last guess was rejected
+            if !link.answered {
+                let _ = link.send("INVALID");
+            }
+            link.answered = false;
+        }
+        // a bot that quit ends its input, like the end of stdin for a player
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_lowercase()))
+    }
+}
+
+fn start_line(word_length: usize, difficult: bool) -> String {
+    format!(
+        "START {word_length} {} {}",
+        game::MAX_ATTEMPTS,
+        difficult as u8
+    )
+}
+
+fn result_line(win: bool, attempts: usize, ans: &str) -> String {
+    if win {
+        format!("RESULT WIN {attempts}")
+    } else {
+        format!("RESULT LOSE {}", ans.to_uppercase())
+    }
+}
+
+fn spawn_bot(command: &str) -> io::Result<Child> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+}
+
+/// Let an external program play. Without `--word`, one game is played for each answer of
+/// the final list, starting at `--day`, until `--rounds` games are played.
+///
+/// The bot reads these lines on its stdin and answers each `START`, `FEEDBACK` or `INVALID`
+/// with a guess on its stdout:
+/// - `START <word length> <max attempts> <difficult 0|1>` when a game starts
+/// - `FEEDBACK <G/Y/R/X codes>` after a valid guess, unless the game is over
+/// - `INVALID` after a rejected guess
+/// - `RESULT WIN <attempts>` or `RESULT LOSE <answer>` when a game ends
+/// - `END` when there are no more games
+///
+/// A bot that quits in the middle of a game abandons it, and no more games are played.
+pub fn run_bot(
+    renderer: &dyn Renderer,
+    args: &Args,
+    game_recorder: &mut GameRecorder,
+    final_words: &WordList,
+    acceptable: &WordList,
+    game_data: &mut GameData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = spawn_bot(args.bot.as_ref().unwrap())?;
+    let link = Rc::new(RefCell::new(BotLink {
+        stdin: child.stdin.take().unwrap(),
+        answered: true,
+    }));
+    let mut input = BotInput {
+        stdout: BufReader::new(child.stdout.take().unwrap()),
+        link: Rc::clone(&link),
+    };
+
+    let answers: Vec<String> = match &args.word {
+        Some(word) => vec![word.clone()],
+        None => {
+            let rounds = args.rounds.unwrap_or(final_words.len());
+            let offset = args.day.unwrap_or(1) - 1;
+            (0..rounds)
+                .map(|round| {
+                    final_words
+                        .get((offset + round) % final_words.len())
+                        .to_string()
+                })
+                .collect()
+        }
+    };
+
+    let mut quit = false;
+    for ans in answers {
+        link.borrow_mut()
+            .send(&start_line(ans.len(), args.difficult))?;
+        let mut attempts = 0;
+        let (guess_results, outcome) = game::play_one_game(
+            renderer,
+            &mut input,
+            args,
+            acceptable,
+            &ans,
+            &mut |guess_result: &GuessResult| {
+                let mut link = link.borrow_mut();
+                link.answered = true;
+                attempts += 1;
+                let codes = guess_result.codes();
+                if attempts < game::MAX_ATTEMPTS && codes.chars().any(|code| code != 'G') {
+                    // a bot that quit is noticed when its next guess is read
+                    let _ = link.send(&format!("FEEDBACK {codes}"));
+                }
+            },
+        )?;
+        game::record_game(
+            args,
+            game_recorder,
+            game_data,
+            &guess_results,
+            &ans,
+            args.difficult,
+            outcome,
+        );
+        if outcome == Outcome::Abandoned {
+            quit = true;
+            break;
+        }
+
+        let mut link = link.borrow_mut();
+        link.answered = true;
+        link.send(&result_line(
+            outcome == Outcome::Won,
+            guess_results.history.len(),
+            &ans,
+        ))?;
+    }
+
+    if !quit {
+        link.borrow_mut().send("END")?;
+    }
+    // close the bot's stdin, in case it reads until the end
+    drop(input);
+    drop(link);
+    child.wait()?;
+    Ok(())
+}
+
+/// An external program speaking the `--bot` protocol, as a tournament strategy
+pub struct ExternalSolver {
+    name: String,
+    child: Child,
+    /// Taken when the solver is dropped, to close the bot's stdin
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl ExternalSolver {
+    pub fn new(name: &str, command: &str) -> io::Result<Self> {
+        let mut child = spawn_bot(command)?;
+        Ok(Self {
+            name: name.to_string(),
+            stdin: child.stdin.take(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+        })
+    }
+
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        let stdin = self.stdin.as_mut().unwrap();
+        writeln!(stdin, "{line}")?;
+        stdin.flush()
+    }
+}
+
+impl Solver for ExternalSolver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self, word_length: usize, difficult: bool) -> io::Result<()> {
+        self.send(&start_line(word_length, difficult))
+    }
+
+    fn guess(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("{} stopped guessing", self.name),
+            ));
+        }
+        Ok(line.trim().to_lowercase())
+    }
+
+    fn feedback(&mut self, _guess: &str, feedback: Feedback) -> io::Result<()> {
+        self.send(&format!("FEEDBACK {feedback}"))
+    }
+
+    fn rejected(&mut self, _guess: &str) -> io::Result<()> {
+        self.send("INVALID")
+    }
+
+    fn finish(&mut self, win: bool, attempts: usize, ans: &str) -> io::Result<()> {
+        self.send(&result_line(win, attempts, ans))
+    }
+}
+
+impl Drop for ExternalSolver {
+    fn drop(&mut self) {
+        // the bot may already be gone, and there is nothing left to tell it then
+        let _ = self.send("END");
+        self.stdin.take();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,74 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Where computed data is cached: `$XDG_CACHE_HOME/wordle`, or `~/.cache/wordle`
+pub fn cache_dir() -> Option<PathBuf> {
+    let cache_home = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(cache_home.join("wordle"))
+}
+
+/// A hash of a word list, so a cache computed from another list is not used. This is
+/// 64-bit FNV-1a, which unlike `DefaultHasher` stays the same across Rust releases.
+pub fn hash_words<'a>(words: impl IntoIterator<Item = &'a str>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET_BASIS;
+    for word in words {
+        // each word ends with a newline, so `ab c` and `a bc` differ
+        for byte in word.bytes().chain([b'\n']) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+#[derive(Serialize, serde::Deserialize)]
+struct Cached<T> {
+    hash: u64,
+    data: T,
+}
+
+/// The data cached in `path`, if it was computed from words hashing to `hash`
+pub fn load<T: DeserializeOwned>(path: &Path, hash: u64) -> Option<T> {
+    let file = File::open(path).ok()?;
+    let cached: Cached<T> = serde_json::from_reader(BufReader::new(file)).ok()?;
+    (cached.hash == hash).then_some(cached.data)
+}
+
+/// Cache `data` in `path`. Failing to write only means it is computed again next time.
+pub fn store<T: Serialize>(path: &Path, hash: u64, data: T) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(content) = serde_json::to_string(&Cached { hash, data }) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
+/// Like [`load`], for data too large for JSON: the hash in little endian, then the bytes
+pub fn load_bytes(path: &Path, hash: u64) -> Option<Vec<u8>> {
+    let content = std::fs::read(path).ok()?;
+    let (stored_hash, data) = content.split_first_chunk::<8>()?;
+    (u64::from_le_bytes(*stored_hash) == hash).then(|| data.to_vec())
+}
+
+/// Like [`store`], but written to a temporary file first, so a concurrent
+/// reader never sees half of the data
+pub fn store_bytes(path: &Path, hash: u64, data: &[u8]) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let temporary = path.with_extension(format!("tmp{}", std::process::id()));
+    let mut content = hash.to_le_bytes().to_vec();
+    content.extend_from_slice(data);
+    if std::fs::write(&temporary, content).is_ok() {
+        let _ = std::fs::rename(&temporary, path);
+    }
+}
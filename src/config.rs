@@ -0,0 +1,325 @@
+use crate::args::Args;
+use crate::dictionaries;
+use crate::messages::{self, Lang};
+use crate::rating::Difficulty;
+use crate::render::{ColorChoice, Format, Layout, RendererKind};
+use crate::theme::ThemeName;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_SEED: u64 = 114514;
+const SYSTEM_CONFIG_DIR: &str = "/etc/wordle";
+/// Config file names searched in the system and user config directories
+const CONFIG_NAMES: &[&str] = &["config.toml", "config.yaml", "config.yml", "config.json"];
+const ENV_PREFIX: &str = "WORDLE_";
+
+/// Every option that can be set outside of the command line.
+/// Unset fields leave the value of a lower layer untouched.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub word: Option<String>,
+    pub random: Option<bool>,
+    pub difficult: Option<bool>,
+    pub stats: Option<bool>,
+    pub timestamps: Option<bool>,
+    pub day: Option<usize>,
+    pub seed: Option<u64>,
+    pub no_repeat: Option<bool>,
+    pub analysis: Option<bool>,
+    pub difficulty: Option<Difficulty>,
+    pub dictionary: Option<String>,
+    pub final_set: Option<String>,
+    pub acceptable_set: Option<String>,
+    pub state: Option<String>,
+    pub backups: Option<bool>,
+    pub host: Option<String>,
+    pub join: Option<String>,
+    pub players: Option<usize>,
+    pub name: Option<String>,
+    pub lang: Option<Lang>,
+    pub theme: Option<ThemeName>,
+    pub renderer: Option<RendererKind>,
+    pub color: Option<ColorChoice>,
+    pub layout: Option<Layout>,
+    pub format: Option<Format>,
+    pub input: Option<String>,
+    pub bot: Option<String>,
+    pub rounds: Option<usize>,
+}
+
+impl Config {
+    fn defaults() -> Self {
+        Self {
+            random: Some(false),
+            difficult: Some(false),
+            stats: Some(false),
+            timestamps: Some(false),
+            day: Some(1),
+            seed: Some(DEFAULT_SEED),
+            no_repeat: Some(false),
+            analysis: Some(false),
+            backups: Some(false),
+            dictionary: Some(dictionaries::default_name().to_string()),
+            players: Some(1),
+            lang: Some(Lang::En),
+            theme: Some(ThemeName::Classic),
+            renderer: Some(RendererKind::Auto),
+            color: Some(ColorChoice::Auto),
+            layout: Some(Layout::Auto),
+            format: Some(Format::Auto),
+            input: Some("-".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Only the options actually given on the command line
+    fn from_args(args: &Args) -> Self {
+        Self {
+            word: args.word.clone(),
+            random: args.random.then_some(true),
+            difficult: args.difficult.then_some(true),
+            stats: args.stats.then_some(true),
+            timestamps: args.timestamps.then_some(true),
+            day: args.day,
+            seed: args.seed,
+            no_repeat: args.no_repeat.then_some(true),
+            analysis: args.analysis.then_some(true),
+            difficulty: args.difficulty,
+            dictionary: args.dictionary.clone(),
+            final_set: args.final_set.clone(),
+            acceptable_set: args.acceptable_set.clone(),
+            state: args.state.clone(),
+            backups: args.backups.then_some(true),
+            host: args.host.clone(),
+            join: args.join.clone(),
+            players: args.players,
+            name: args.name.clone(),
+            lang: args.lang,
+            theme: args.theme,
+            renderer: args.renderer,
+            color: args.color,
+            layout: args.layout,
+            format: args.format,
+            input: args.input.clone(),
+            bot: args.bot.clone(),
+            rounds: args.rounds,
+        }
+    }
+
+    fn apply(self, args: &mut Args) {
+        args.word = self.word;
+        args.random = self.random.unwrap_or_default();
+        args.difficult = self.difficult.unwrap_or_default();
+        args.stats = self.stats.unwrap_or_default();
+        args.timestamps = self.timestamps.unwrap_or_default();
+        args.day = self.day;
+        args.seed = self.seed;
+        args.no_repeat = self.no_repeat.unwrap_or_default();
+        args.analysis = self.analysis.unwrap_or_default();
+        args.difficulty = self.difficulty;
+        args.dictionary = self.dictionary;
+        args.final_set = self.final_set;
+        args.acceptable_set = self.acceptable_set;
+        args.state = self.state;
+        args.backups = self.backups.unwrap_or_default();
+        args.host = self.host;
+        args.join = self.join;
+        args.players = self.players;
+        args.name = self.name;
+        args.lang = self.lang;
+        args.theme = self.theme;
+        args.renderer = self.renderer;
+        args.color = self.color;
+        args.layout = self.layout;
+        args.format = self.format;
+        args.input = self.input;
+        args.bot = self.bot;
+        args.rounds = self.rounds;
+    }
+
+    /// Read a TOML, YAML or JSON config file, chosen by its extension
+    fn read(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let config = match extension {
+            Some("toml") => {
+                toml::from_str(&std::fs::read_to_string(path)?).map_err(|err| err.to_string())
+            }
+            Some("yaml" | "yml") => serde_yaml::from_reader(BufReader::new(File::open(path)?))
+                .map_err(|err| err.to_string()),
+            _ => serde_json::from_reader(BufReader::new(File::open(path)?))
+                .map_err(|err| err.to_string()),
+        };
+        config.map_err(|err| format!("Invalid config file {}: {err}", path.display()).into())
+    }
+}
+
+/// Where the effective value of a setting comes from
+#[derive(Debug, Clone)]
+pub enum Source {
+    Default,
+    Locale(String),
+    SystemConfig(PathBuf),
+    UserConfig(PathBuf),
+    ConfigFile(String),
+    Env(String),
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::Locale(var) => write!(f, "locale {var}"),
+            Source::SystemConfig(path) => write!(f, "system config {}", path.display()),
+            Source::UserConfig(path) => write!(f, "user config {}", path.display()),
+            Source::ConfigFile(path) => write!(f, "--config {path}"),
+            Source::Env(var) => write!(f, "environment {var}"),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The source of every setting that is not a default
+pub type Provenance = BTreeMap<String, Source>;
+
+/// The first config file that exists in `dir`
+fn find_config(dir: &Path) -> Option<PathBuf> {
+    CONFIG_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+fn user_config_dir() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join("wordle"))
+}
+
+/// The names of all settings, in the order they are serialized
+fn setting_names() -> Vec<String> {
+    match serde_json::to_value(Config::default()) {
+        Ok(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// One layer per `WORDLE_*` variable, so each is reported on its own
+fn env_layers() -> Result<Vec<(Source, Config)>, Box<dyn std::error::Error>> {
+    let mut layers = Vec::new();
+    for name in setting_names() {
+        let var = format!("{ENV_PREFIX}{}", name.to_uppercase());
+        let Ok(raw) = std::env::var(&var) else {
+            continue;
+        };
+        // the value is taken as a string, unless the setting is a number or a boolean
+        let layer = |value| {
+            let mut layer = Map::new();
+            layer.insert(name.clone(), value);
+            serde_json::from_value::<Config>(Value::Object(layer))
+        };
+        let config = layer(Value::String(raw.clone()))
+            .or_else(|err| {
+                serde_json::from_str(&raw)
+                    .ok()
+                    .and_then(|value| layer(value).ok())
+                    .ok_or(err)
+            })
+            .map_err(|err| format!("Invalid value in {var}: {err}"))?;
+        layers.push((Source::Env(var), config));
+    }
+    Ok(layers)
+}
+
+/// Resolve every setting from, in increasing precedence: defaults, the locale, the system
+/// config, the user config, `--config`, `WORDLE_*` environment variables and the command line
+pub fn load_config(args: &mut Args) -> Result<Provenance, Box<dyn std::error::Error>> {
+    let mut layers = vec![(Source::Default, Config::defaults())];
+
+    if let Some((var, lang)) = messages::locale_lang() {
+        let config = Config {
+            lang: Some(lang),
+            ..Default::default()
+        };
+        layers.push((Source::Locale(var), config));
+    }
+    if let Some(system_path) = find_config(Path::new(SYSTEM_CONFIG_DIR)) {
+        layers.push((
+            Source::SystemConfig(system_path.clone()),
+            Config::read(&system_path)?,
+        ));
+    }
+    if let Some(user_path) = user_config_dir().and_then(|dir| find_config(&dir)) {
+        layers.push((
+            Source::UserConfig(user_path.clone()),
+            Config::read(&user_path)?,
+        ));
+    }
+    if let Some(config_path) = &args.config {
+        layers.push((
+            Source::ConfigFile(config_path.clone()),
+            Config::read(Path::new(config_path))?,
+        ));
+    }
+    layers.append(&mut env_layers()?);
+    layers.push((Source::CommandLine, Config::from_args(args)));
+
+    let mut merged = Map::new();
+    let mut provenance = Provenance::new();
+    for (source, config) in layers {
+        let Value::Object(layer) = serde_json::to_value(config)? else {
+            continue;
+        };
+        for (name, value) in layer {
+            if value.is_null() {
+                continue;
+            }
+            // a fixed answer and a random one override each other
+            if name == "word" {
+                if merged.insert("random".to_string(), Value::Bool(false))
+                    == Some(Value::Bool(true))
+                {
+                    provenance.insert("random".to_string(), source.clone());
+                }
+            } else if name == "random"
+                && value == Value::Bool(true)
+                && merged.remove("word").is_some()
+            {
+                provenance.insert("word".to_string(), source.clone());
+            }
+            merged.insert(name.clone(), value);
+            provenance.insert(name, source.clone());
+        }
+    }
+
+    let config: Config = serde_json::from_value(Value::Object(merged))?;
+    config.apply(args);
+    Ok(provenance)
+}
+
+/// Print the effective value of every setting and where it comes from
+pub fn print_config(args: &Args, provenance: &Provenance) {
+    let mut effective = Config::from_args(args);
+    effective.random = Some(args.random);
+    effective.difficult = Some(args.difficult);
+    effective.stats = Some(args.stats);
+    effective.timestamps = Some(args.timestamps);
+    effective.no_repeat = Some(args.no_repeat);
+    effective.analysis = Some(args.analysis);
+    effective.backups = Some(args.backups);
+    let Ok(Value::Object(effective)) = serde_json::to_value(effective) else {
+        return;
+    };
+    for (name, value) in effective {
+        let source = provenance.get(&name).unwrap_or(&Source::Default);
+        println!("{name} = {value} ({source})");
+    }
+}
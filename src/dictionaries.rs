@@ -0,0 +1,68 @@
+use std::io::Write;
+
+/// A built-in pair of word lists, compiled in from `dictionaries/` by the build script
+pub struct Dictionary {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub final_words: &'static [&'static str],
+    pub acceptable: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/dictionaries.rs"));
+
+/// The dictionary used when none is given
+pub fn default_name() -> &'static str {
+    DICTIONARIES[0].name
+}
+
+pub fn find(name: &str) -> Result<&'static Dictionary, Box<dyn std::error::Error>> {
+    DICTIONARIES
+        .iter()
+        .find(|dictionary| dictionary.name == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = DICTIONARIES
+                .iter()
+                .map(|dictionary| dictionary.name)
+                .collect();
+            format!(
+                "Unknown dictionary {name}, expected one of {}",
+                names.join(", ")
+            )
+            .into()
+        })
+}
+
+/// Print the name, sizes and description of every dictionary
+pub fn print_list() {
+    println!(
+        "{:<10} {:>6} {:>10}  description",
+        "name", "final", "acceptable"
+    );
+    for dictionary in DICTIONARIES {
+        println!(
+            "{:<10} {:>6} {:>10}  {}",
+            dictionary.name,
+            dictionary.final_words.len(),
+            dictionary.acceptable.len(),
+            dictionary.description
+        );
+    }
+}
+
+/// Print the final or acceptable words of a dictionary, one per line
+pub fn print_words(name: &str, acceptable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let dictionary = find(name)?;
+    let words = if acceptable {
+        dictionary.acceptable
+    } else {
+        dictionary.final_words
+    };
+    let mut stdout = std::io::stdout().lock();
+    for word in words {
+        // stop quietly when piped into e.g. `head`
+        if writeln!(stdout, "{word}").is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::args::ExportArgs;
+use crate::game::MAX_ATTEMPTS;
+use crate::messages;
+use crate::recorder::{GameData, GameRecorder};
+use clap::ValueEnum;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Longest bar of the guess distribution in the Markdown report
+const BAR_WIDTH: u32 = 20;
+/// Most tried words listed in the Markdown report
+const TOP_WORDS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// The state file itself
+    Json,
+    /// One row per game
+    Csv,
+    /// A summary report with the guess distribution
+    Markdown,
+}
+
+/// The format given by `--format`, or else by the extension of the output file
+fn format_of(export: &ExportArgs) -> ExportFormat {
+    if let Some(format) = export.format {
+        return format;
+    }
+    let extension = export
+        .output
+        .as_ref()
+        .and_then(|path| Path::new(path).extension())
+        .and_then(|extension| extension.to_str());
+    match extension {
+        Some("csv") => ExportFormat::Csv,
+        Some("md" | "markdown") => ExportFormat::Markdown,
+        _ => ExportFormat::Json,
+    }
+}
+
+/// The UTC date of a time in milliseconds since the Unix epoch, e.g. `2024-02-29`
+fn date(played_at: u64) -> String {
+    // from the days since 1970-01-01 to the civil date, as in Howard Hinnant's
+    // `civil_from_days`, counting years from March so that leap days come last
+    let days = played_at / 86_400_000 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = (month_from_march + 2) % 12 + 1;
+    let year = era * 400 + year_of_era + (month <= 2) as u64;
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn csv(game_data: &GameData) -> String {
+    let mut content =
+        String::from("game,answer,guesses,attempts,win,abandoned,mode,date,day,feedback\n");
+    for (idx, game) in game_data.games.iter().enumerate() {
+        let _ = writeln!(
+            content,
+            "{},{},{},{},{},{},{},{},{},{}",
+            idx + 1,
+            game.answer.to_uppercase(),
+            game.guesses.join(" ").to_uppercase(),
+            game.attempts(),
+            game.is_win(),
+            game.abandoned,
+            if game.difficult {
+                "difficult"
+            } else {
+                "normal"
+            },
+            game.played_at.map(date).unwrap_or_default(),
+            game.day.map(|day| day.to_string()).unwrap_or_default(),
+            game.feedback.join(" ")
+        );
+    }
+    content
+}
+
+fn markdown(recorder: &GameRecorder) -> String {
+    let (wins, losses, abandoned) = (recorder.wins(), recorder.losses(), recorder.abandoned());
+    let games = wins + losses + abandoned;
+    // of the games that were over
+    let win_rate = if wins + losses == 0 {
+        0.0
+    } else {
+        100.0 * wins as f64 / (wins + losses) as f64
+    };
+    let messages = messages::get();
+    let mut content = format!("# {}\n\n", messages.report_title());
+    let _ = writeln!(
+        content,
+        "| {} |",
+        messages.report_summary_columns().join(" | ")
+    );
+    content += "| ---: | ---: | ---: | ---: | ---: | ---: |\n";
+    let _ = writeln!(
+        content,
+        "| {games} | {wins} | {losses} | {abandoned} | {win_rate:.1}% | {:.2} |",
+        recorder.average_attempts()
+    );
+
+    let _ = writeln!(content, "\n## {}\n", messages.report_distribution());
+    let [attempts, games] = messages.report_distribution_columns();
+    let _ = writeln!(content, "| {attempts} | {games} | |");
+    content += "| :--- | ---: | :--- |\n";
+    let distribution = recorder.distribution();
+    let most = distribution
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    for (idx, count) in distribution.iter().enumerate() {
+        let attempts = if idx < MAX_ATTEMPTS as usize {
+            (idx + 1).to_string()
+        } else {
+            "X".to_string()
+        };
+        // every game that happened gets at least one block
+        let bar = "█".repeat((count * BAR_WIDTH).div_ceil(most) as usize);
+        let _ = writeln!(content, "| {attempts} | {count} | {bar} |");
+    }
+
+    let _ = writeln!(content, "\n## {}\n", messages.report_top_words());
+    let _ = writeln!(
+        content,
+        "| {} |",
+        messages.report_top_words_columns().join(" | ")
+    );
+    content += "| :--- | ---: |\n";
+    for (word, count) in recorder.top_words(TOP_WORDS) {
+        let _ = writeln!(content, "| {} | {count} |", word.to_uppercase());
+    }
+    content
+}
+
+/// Write the games of the state file as JSON, CSV or a Markdown report
+pub fn run(
+    export: &ExportArgs,
+    game_data: &GameData,
+    recorder: &GameRecorder,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = match format_of(export) {
+        ExportFormat::Json => serde_json::to_string_pretty(game_data)? + "\n",
+        ExportFormat::Csv => csv(game_data),
+        ExportFormat::Markdown => markdown(recorder),
+    };
+    match &export.output {
+        Some(path) => std::fs::write(path, content)?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use crate::game::{CharStatus, MAX_WORD_LENGTH};
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of different feedbacks for words of `word_length` letters,
+/// each letter being green, yellow or grey
+pub fn feedbacks(word_length: usize) -> usize {
+    3usize.pow(word_length as u32)
+}
+
+/// The colours of one row, e.g. `GYRRG`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Feedback {
+    /// Grey after the end of the row, so equal rows compare equal
+    status: [CharStatus; MAX_WORD_LENGTH],
+    len: usize,
+}
+
+impl Feedback {
+    /// The row of `status`, which must not be longer than [`MAX_WORD_LENGTH`]
+    pub fn new(status: &[CharStatus]) -> Self {
+        let mut padded = [CharStatus::TooMany; MAX_WORD_LENGTH];
+        padded[..status.len()].copy_from_slice(status);
+        Self {
+            status: padded,
+            len: status.len(),
+        }
+    }
+
+    pub fn status(&self) -> &[CharStatus] {
+        &self.status[..self.len]
+    }
+
+    pub fn is_win(&self) -> bool {
+        self.status()
+            .iter()
+            .all(|status| *status == CharStatus::Correct)
+    }
+
+    /// The feedback as a base 3 number below [`feedbacks`], grey being 0, yellow 1 and green 2
+    pub fn encode(&self) -> u16 {
+        self.status().iter().fold(0, |code, status| {
+            code * 3
+                + match status {
+                    CharStatus::Correct => 2,
+                    CharStatus::WrongPosition => 1,
+                    _ => 0,
+                }
+        })
+    }
+
+    /// The row as in a shared result, e.g. `🟩🟨⬛⬛🟩`
+    pub fn emoji(&self) -> String {
+        self.status()
+            .iter()
+            .map(|status| match status {
+                CharStatus::Correct => '🟩',
+                CharStatus::WrongPosition => '🟨',
+                _ => '⬛',
+            })
+            .collect()
+    }
+}
+
+/// Prints the `G/Y/R` codes
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for status in self.status() {
+            write!(f, "{}", status.code())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `G/Y/R/X` codes, where `X` is grey too, or the emoji of a shared result,
+/// including the high contrast ones
+impl FromStr for Feedback {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid feedback {text}, expected e.g. GYRRG or 🟩🟨⬛⬛🟩");
+        let mut status = Vec::new();
+        for letter in text.trim().chars() {
+            status.push(match letter {
+                '🟩' | '🟧' => CharStatus::Correct,
+                '🟨' | '🟦' => CharStatus::WrongPosition,
+                '⬛' | '⬜' => CharStatus::TooMany,
+                code => match CharStatus::from_code(code.to_ascii_uppercase()) {
+                    Some(CharStatus::Unknown) => CharStatus::TooMany,
+                    Some(status) => status,
+                    None => return Err(invalid()),
+                },
+            });
+        }
+        if status.is_empty() || status.len() > MAX_WORD_LENGTH {
+            return Err(invalid());
+        }
+        Ok(Self::new(&status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The feedback of `word_length` letters encoded as `code`, the inverse of [`Feedback::encode`]
+    fn decode(word_length: usize, mut code: usize) -> Feedback {
+        let mut status = vec![CharStatus::TooMany; word_length];
+        for status in status.iter_mut().rev() {
+            *status = match code % 3 {
+                2 => CharStatus::Correct,
+                1 => CharStatus::WrongPosition,
+                _ => CharStatus::TooMany,
+            };
+            code /= 3;
+        }
+        Feedback::new(&status)
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        for word_length in [4, 5, 6] {
+            for code in 0..feedbacks(word_length) {
+                let feedback = decode(word_length, code);
+                assert_eq!(feedback.encode() as usize, code, "{feedback}");
+                // the codes and the emoji parse back to the same feedback
+                assert_eq!(feedback.to_string().parse(), Ok(feedback));
+                assert_eq!(feedback.emoji().parse(), Ok(feedback));
+            }
+            assert!(decode(word_length, feedbacks(word_length) - 1).is_win());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = Feedback::new(&[
+            CharStatus::Correct,
+            CharStatus::WrongPosition,
+            CharStatus::TooMany,
+            CharStatus::TooMany,
+            CharStatus::Correct,
+        ]);
+        for text in ["GYRRG", "gyxrg", " GYXXG\n", "🟩🟨⬛⬜🟩", "🟧🟦⬛⬛🟧"] {
+            assert_eq!(text.parse(), Ok(expected), "{text}");
+        }
+        assert_eq!(expected.to_string(), "GYRRG");
+        assert_eq!(expected.emoji(), "🟩🟨⬛⬛🟩");
+        // a row may be longer or shorter, but not longer than any word
+        assert_eq!("GYRR".parse::<Feedback>().unwrap().status().len(), 4);
+        for text in ["GYRRGGYRRGG", "GYRRB", ""] {
+            assert!(text.parse::<Feedback>().is_err(), "{text}");
+        }
+    }
+}
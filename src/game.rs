@@ -1,29 +1,57 @@
+use crate::analysis;
 use crate::args::Args;
-use crate::args::Config;
-use crate::builtin_words;
-use crate::recorder::{GameData, GameRecorder, SingleGameData};
-use colored::Colorize;
+use crate::dictionaries;
+use crate::feedback::Feedback;
+use crate::input::Input;
+use crate::matrix::FeedbackMatrix;
+use crate::messages;
+use crate::rating;
+use crate::recorder::{GameData, GameRecorder, UnfinishedGame};
+use crate::render::Renderer;
+use crate::words::WordList;
+use clap::ValueEnum;
 use itertools::izip;
 use rand::SeedableRng;
-use rand::prelude::SliceRandom;
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io;
 
-const MAX_ATTEMPTS: u32 = 6;
-const WORD_LENGTH: usize = 5;
+pub const MAX_ATTEMPTS: u32 = 6;
+/// The longest words of a list, as word lists and feedbacks are stored in arrays of this size
+pub const MAX_WORD_LENGTH: usize = 8;
 
-#[derive(Copy, Clone, PartialEq)]
-enum CharStatus {
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CharStatus {
     Correct,
     WrongPosition,
     TooMany,
     Unknown,
 }
 
+impl CharStatus {
+    /// The `G/Y/R/X` code of the status
+    pub fn code(self) -> char {
+        match self {
+            CharStatus::Correct => 'G',
+            CharStatus::WrongPosition => 'Y',
+            CharStatus::TooMany => 'R',
+            CharStatus::Unknown => 'X',
+        }
+    }
+
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'G' => Some(CharStatus::Correct),
+            'Y' => Some(CharStatus::WrongPosition),
+            'R' => Some(CharStatus::TooMany),
+            'X' => Some(CharStatus::Unknown),
+            _ => None,
+        }
+    }
+}
+
 pub struct GuessResult {
     pub content: String,
-    status: [CharStatus; WORD_LENGTH],
+    status: Vec<CharStatus>,
     keyboard: BTreeMap<char, CharStatus>,
 }
 
@@ -31,7 +59,7 @@ impl GuessResult {
     fn new(_content: &str) -> Self {
         let mut new_self = Self {
             content: _content.to_string(),
-            status: [CharStatus::Unknown; WORD_LENGTH],
+            status: vec![CharStatus::Unknown; _content.chars().count()],
             keyboard: BTreeMap::new(),
         };
 
@@ -46,79 +74,26 @@ impl GuessResult {
     fn clone(&self, _content: &str) -> Self {
         Self {
             content: _content.to_string(),
-            status: [CharStatus::Unknown; WORD_LENGTH],
+            status: vec![CharStatus::Unknown; _content.chars().count()],
             keyboard: self.keyboard.clone(),
         }
     }
 
-    fn print(&self, is_tty: bool) {
-        for (status, guess_char) in self.status.iter().zip(self.content.chars()) {
-            match status {
-                CharStatus::Correct => {
-                    if is_tty {
-                        print!("{}", guess_char.to_string().to_uppercase().color("green"))
-                    } else {
-                        print!("G")
-                    }
-                }
-                CharStatus::WrongPosition => {
-                    if is_tty {
-                        print!("{}", guess_char.to_string().to_uppercase().color("yellow"))
-                    } else {
-                        print!("Y")
-                    }
-                }
-                CharStatus::TooMany => {
-                    if is_tty {
-                        print!("{}", guess_char.to_string().to_uppercase().color("red"))
-                    } else {
-                        print!("R")
-                    }
-                }
-                CharStatus::Unknown => {
-                    if is_tty {
-                        print!("{}", guess_char.to_string().to_uppercase())
-                    } else {
-                        print!("X")
-                    }
-                }
-            }
-        }
-        print!(" ");
+    pub fn status(&self) -> &[CharStatus] {
+        &self.status
+    }
 
-        for (key, status) in self.keyboard.iter() {
-            match status {
-                CharStatus::Correct => {
-                    if is_tty {
-                        print!("{}", key.to_string().to_uppercase().color("green"))
-                    } else {
-                        print!("G")
-                    }
-                }
-                CharStatus::WrongPosition => {
-                    if is_tty {
-                        print!("{}", key.to_string().to_uppercase().color("yellow"))
-                    } else {
-                        print!("Y")
-                    }
-                }
-                CharStatus::TooMany => {
-                    if is_tty {
-                        print!("{}", key.to_string().to_uppercase().color("red"))
-                    } else {
-                        print!("R")
-                    }
-                }
-                CharStatus::Unknown => {
-                    if is_tty {
-                        print!("{}", key.to_string().to_uppercase())
-                    } else {
-                        print!("X")
-                    }
-                }
-            }
-        }
-        println!();
+    pub fn keyboard(&self) -> &BTreeMap<char, CharStatus> {
+        &self.keyboard
+    }
+
+    pub fn feedback(&self) -> Feedback {
+        Feedback::new(&self.status)
+    }
+
+    /// The status of each char as `G/Y/R/X` codes, without the letters
+    pub fn codes(&self) -> String {
+        self.feedback().to_string()
     }
 }
 
@@ -141,14 +116,14 @@ impl Guess {
         }
     }
 
-    fn print(&self, is_tty: bool) {
-        if is_tty {
-            for guess in &self.history {
-                guess.print(is_tty);
-            }
-        } else {
-            self.history.last().unwrap().print(is_tty);
+    /// Check `guess` against `ans` and append it, unless difficult mode rejects it.
+    /// Returns whether the game is won, or `None` if the guess is rejected.
+    pub fn submit(&mut self, is_difficult: bool, guess: &str, ans: &str) -> Option<bool> {
+        if !self.difficult_check(is_difficult, guess) {
+            return None;
         }
+        self.append(guess);
+        Some(AnsChecker::new(ans).check(self.history.last_mut().unwrap()))
     }
 
     /// check if the new guess is valid in difficult mode
@@ -266,114 +241,333 @@ impl<'a> AnsChecker<'a> {
     }
 }
 
+/// The feedback of `guess` against `ans`, following the same rules as [`AnsChecker`],
+/// but without allocating, as solvers call it for every pair of words. Both must be
+/// lowercase words of the same length.
+pub fn score(ans: &str, guess: &str) -> Result<Feedback, String> {
+    let (ans, guess) = (ans.as_bytes(), guess.as_bytes());
+    let length = ans.len();
+    let is_word = |word: &[u8]| {
+        word.len() == length && length <= MAX_WORD_LENGTH && word.iter().all(u8::is_ascii_lowercase)
+    };
+    if !is_word(ans) || !is_word(guess) {
+        return Err(format!(
+            "Cannot score {} against {}, expected lowercase words of the same length",
+            String::from_utf8_lossy(guess),
+            String::from_utf8_lossy(ans)
+        ));
+    }
+
+    let mut status = [CharStatus::TooMany; MAX_WORD_LENGTH];
+    let mut counts = [0u8; 26];
+    for idx in 0..length {
+        if guess[idx] == ans[idx] {
+            status[idx] = CharStatus::Correct;
+        } else {
+            counts[(ans[idx] - b'a') as usize] += 1;
+        }
+    }
+    for idx in 0..length {
+        let count = &mut counts[(guess[idx] - b'a') as usize];
+        if status[idx] != CharStatus::Correct && *count > 0 {
+            status[idx] = CharStatus::WrongPosition;
+            *count -= 1;
+        }
+    }
+    Ok(Feedback::new(&status[..length]))
+}
+
 pub fn init_game(
     args: &Args,
-    final_words: &mut Vec<String>,
-    acceptable: &mut Vec<String>,
+    final_words: &mut WordList,
+    acceptable: &mut WordList,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if args.final_set.is_some() {
-        let final_set_file = File::open(&args.final_set.as_ref().unwrap())?;
-        *final_words = io::BufReader::new(final_set_file)
-            .lines()
-            .map(|line| line.unwrap())
-            .collect();
+    let dictionary = dictionaries::find(
+        args.dictionary
+            .as_deref()
+            .unwrap_or(dictionaries::default_name()),
+    )?;
+    if let Some(final_set) = &args.final_set {
+        *final_words = WordList::read(final_set)?;
     } else {
-        *final_words = builtin_words::FINAL
-            .iter()
-            .map(|word| word.to_string())
-            .collect();
+        *final_words = WordList::from_words(dictionary.final_words.iter().copied())?;
     }
 
-    if args.acceptable_set.is_some() {
-        let acceptable_set_file = File::open(&args.acceptable_set.as_ref().unwrap())?;
-        *acceptable = io::BufReader::new(acceptable_set_file)
-            .lines()
-            .map(|line| line.unwrap())
-            .collect();
+    if let Some(acceptable_set) = &args.acceptable_set {
+        *acceptable = WordList::read(acceptable_set)?;
     } else {
-        *acceptable = builtin_words::ACCEPTABLE
-            .iter()
-            .map(|word| word.to_string())
-            .collect();
+        *acceptable = WordList::from_words(dictionary.acceptable.iter().copied())?;
     }
 
     // check if final is a subset of acceptable
-    let hash_set_acceptable = acceptable.iter().collect::<HashSet<_>>();
-    if !final_words
-        .iter()
-        .all(|word| hash_set_acceptable.contains(word))
-    {
+    if !final_words.is_subset_of(acceptable) {
         return Err("Final words must be a subset of acceptable words!".into());
     }
 
     if args.random {
+        if let Some(difficulty) = args.difficulty {
+            let ratings = rating::ratings(args, final_words);
+            final_words.retain(|word| ratings[word].difficulty == difficulty);
+            if final_words.is_empty() {
+                let name = difficulty.to_possible_value().unwrap();
+                return Err(format!("No final word is rated {}", name.get_name()).into());
+            }
+        }
         init_shuffle(args.seed.unwrap(), final_words);
     }
     Ok(())
 }
 
-fn init_shuffle(seed: u64, final_words: &mut Vec<String>) {
+pub fn init_shuffle(seed: u64, final_words: &mut WordList) {
     let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
     final_words.shuffle(&mut rng);
 }
 
-fn gen_answer(args: &Args, final_words: &Vec<String>) -> String {
+/// The answer of the next game, `None` if the input ended before one was given
+pub fn gen_answer(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    args: &Args,
+    final_words: &WordList,
+    game_data: &GameData,
+) -> io::Result<Option<String>> {
     if args.random {
-        final_words[args.day.unwrap() - 1 % final_words.len()].to_string()
+        let start = (args.day.unwrap() - 1) % final_words.len();
+        if !args.no_repeat {
+            return Ok(Some(final_words.get(start).to_string()));
+        }
+        // take the next answer of the sequence that has not been played yet
+        let played: HashSet<String> = game_data
+            .games
+            .iter()
+            .map(|game| game.answer.to_lowercase())
+            .collect();
+        let unplayed = (0..final_words.len())
+            .map(|offset| final_words.get((start + offset) % final_words.len()))
+            .find(|word| !played.contains(*word));
+        match unplayed {
+            Some(word) => Ok(Some(word.to_string())),
+            None => {
+                eprintln!("{}", messages::get().answers_exhausted());
+                Ok(Some(final_words.get(start).to_string()))
+            }
+        }
     } else {
         if let Some(given_answer) = &args.word {
-            assert!(final_words.contains(&given_answer));
-            given_answer.clone()
+            assert!(final_words.contains(given_answer));
+            Ok(Some(given_answer.clone()))
         } else {
             loop {
-                let mut tmp = String::new();
-                io::stdin().read_line(&mut tmp).unwrap();
-                tmp = tmp.trim().to_string();
+                let Some(line) = input.read_line()? else {
+                    return Ok(None);
+                };
+                let tmp = line.trim().to_string();
                 if final_words.contains(&tmp) {
-                    break tmp;
+                    return Ok(Some(tmp));
                 }
-                println!("INVALID");
+                renderer.message(&messages::get().invalid());
             }
         }
     }
 }
 
+/// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// The input ended before the game was over
+    Abandoned,
+}
+
+/// Ask whether to resume the unfinished game of the state file, `None` if the input ended
+fn ask_resume(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    guesses: usize,
+) -> io::Result<Option<bool>> {
+    if let Some(prompt) = messages::get().resume_prompt(guesses) {
+        renderer.message(&prompt);
+    }
+    loop {
+        let Some(line) = input.read_line()? else {
+            return Ok(None);
+        };
+        match line.trim() {
+            "Y" => return Ok(Some(true)),
+            "N" => return Ok(Some(false)),
+            _ => renderer.message(&messages::get().invalid_input()),
+        }
+    }
+}
+
+/// Play a game, resuming the unfinished game of the state file if the player wants to.
+/// An input that is not interactive is not asked: the unfinished game is resumed if
+/// it has the answer of the new game, and otherwise left untouched, the new game not
+/// saving its progress then. Returns how it ended, or `None` if the input ended before
+/// a guess was made, in which case nothing is recorded.
 pub fn start_one_game(
-    is_tty: bool,
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
     args: &Args,
     game_recorder: &mut GameRecorder,
-    final_words: &Vec<String>,
-    acceptable: &Vec<String>,
+    final_words: &WordList,
+    acceptable: &WordList,
     game_data: &mut GameData,
-) {
-    let mut game_win = false;
+) -> io::Result<Option<Outcome>> {
+    // Resume an interrupted game, or set a new answer
+    let resumed = match game_data.unfinished.clone() {
+        Some(unfinished) if input.is_interactive() => {
+            let Some(resume) = ask_resume(renderer, input, unfinished.guesses.len())? else {
+                return Ok(None);
+            };
+            if !resume {
+                game_data.set_unfinished(None);
+            }
+            resume.then_some(unfinished)
+        }
+        _ => None,
+    };
+    let mut game = match resumed {
+        Some(game) => game,
+        None => {
+            let Some(ans) = gen_answer(renderer, input, args, final_words, game_data)? else {
+                return Ok(None);
+            };
+            let game = UnfinishedGame {
+                answer: ans.to_uppercase(),
+                difficult: args.difficult,
+                guesses: Vec::new(),
+            };
+            // a resumed game keeps the mode it was started in
+            match &game_data.unfinished {
+                Some(unfinished) if unfinished.answer == game.answer => unfinished.clone(),
+                _ => game,
+            }
+        }
+    };
+    // only one game in progress is saved, and it may be another one
+    let saves_progress = args.state.is_some()
+        && game_data
+            .unfinished
+            .as_ref()
+            .is_none_or(|unfinished| *unfinished == game);
+    let ans = game.answer.to_lowercase();
+    let mut history = Guess::new();
+    for guess in &game.guesses {
+        history.submit(game.difficult, &guess.to_lowercase(), &ans);
+    }
 
-    // Set answer
-    let ans = gen_answer(args, final_words);
+    // The progress is saved after every guess, to be resumed if the game is interrupted
+    let (guess_results, outcome) = resume_one_game(
+        renderer,
+        input,
+        game.difficult,
+        acceptable,
+        &ans,
+        history,
+        &mut |guess_result| {
+            if !saves_progress {
+                return;
+            }
+            game.guesses.push(guess_result.content.to_uppercase());
+            game_data.set_unfinished(Some(game.clone()));
+            if let Err(err) = game_data.save(args) {
+                eprintln!("{err}");
+            }
+        },
+    )?;
+    if args.analysis && outcome != Outcome::Abandoned {
+        let guesses: Vec<String> = guess_results
+            .history
+            .iter()
+            .map(|guess_result| guess_result.content.clone())
+            .collect();
+        let matrix = FeedbackMatrix::new(acceptable, final_words);
+        match analysis::analyse(&ans, &guesses, &matrix) {
+            Ok(analyses) => analysis::print(&analyses),
+            Err(err) => eprintln!("{err}"),
+        }
+    }
+    if outcome == Outcome::Abandoned && guess_results.history.is_empty() {
+        return Ok(None);
+    }
+    if saves_progress {
+        game_data.set_unfinished(None);
+    }
+    record_game(
+        args,
+        game_recorder,
+        game_data,
+        &guess_results,
+        &ans,
+        game.difficult,
+        outcome,
+    );
+    Ok(Some(outcome))
+}
 
-    let mut attempt = 0;
-    let mut guess_results = Guess::new();
+/// Play one game against a known answer, calling `on_guess` after every valid guess.
+/// Returns the guesses and how the game ended.
+pub fn play_one_game(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    args: &Args,
+    acceptable: &WordList,
+    ans: &str,
+    on_guess: &mut dyn FnMut(&GuessResult),
+) -> io::Result<(Guess, Outcome)> {
+    let history = Guess::new();
+    resume_one_game(
+        renderer,
+        input,
+        args.difficult,
+        acceptable,
+        ans,
+        history,
+        on_guess,
+    )
+}
+
+/// Like [`play_one_game`], the guesses in `guess_results` being already made
+pub fn resume_one_game(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    difficult: bool,
+    acceptable: &WordList,
+    ans: &str,
+    mut guess_results: Guess,
+    on_guess: &mut dyn FnMut(&GuessResult),
+) -> io::Result<(Guess, Outcome)> {
+    let mut game_win = false;
+    let mut attempt = guess_results.history.len() as u32;
+    if attempt > 0 {
+        renderer.board(&guess_results);
+    }
 
     // Guess until exceeds MAX_ATTEMPTS
     while attempt < MAX_ATTEMPTS {
-        // input guess
-        let guess = loop {
-            let mut tmp: String = String::new();
-            io::stdin().read_line(&mut tmp).unwrap();
-            tmp = tmp.trim().to_string();
-            if acceptable.contains(&tmp) && guess_results.difficult_check(args.difficult, &tmp) {
-                break tmp;
+        // input and check guess
+        game_win = loop {
+            let Some(line) = input.read_line()? else {
+                // the input ended, nothing more can be guessed
+                if attempt > 0 {
+                    renderer.result(Outcome::Abandoned, attempt, ans);
+                }
+                return Ok((guess_results, Outcome::Abandoned));
+            };
+            let tmp = line.trim().to_string();
+            if acceptable.contains(&tmp)
+                && let Some(win) = guess_results.submit(difficult, &tmp, ans)
+            {
+                break win;
             }
-            println!("INVALID");
+            renderer.message(&messages::get().invalid());
         };
-        game_recorder.add_tried_word(guess.clone());
-
-        // check guess
-        guess_results.append(&guess);
-        game_win = AnsChecker::new(&ans).check(&mut guess_results.history.last_mut().unwrap());
 
         // render output
-        guess_results.print(is_tty);
+        renderer.board(&guess_results);
+        on_guess(guess_results.history.last().unwrap());
 
         attempt += 1;
         if game_win {
@@ -381,92 +575,199 @@ pub fn start_one_game(
         }
     }
 
-    // Record this game
-    game_recorder.add_game(game_win, attempt);
-    if args.state.is_some() {
-        game_data.add_game(&guess_results, &ans);
-    }
+    let outcome = if game_win {
+        Outcome::Won
+    } else {
+        Outcome::Lost
+    };
+    renderer.result(outcome, attempt, ans);
+    Ok((guess_results, outcome))
+}
 
-    if game_win {
-        println!("CORRECT {attempt}");
+/// Record a finished game, played in difficult mode or not, in the statistics,
+/// and in the game data if it is saved. A game abandoned before a guess was made
+/// is not recorded.
+pub fn record_game(
+    args: &Args,
+    game_recorder: &mut GameRecorder,
+    game_data: &mut GameData,
+    guess_results: &Guess,
+    ans: &str,
+    difficult: bool,
+    outcome: Outcome,
+) {
+    if outcome == Outcome::Abandoned && guess_results.history.is_empty() {
+        return;
+    }
+    for guess_result in &guess_results.history {
+        game_recorder.add_tried_word(guess_result.content.clone());
+    }
+    if outcome == Outcome::Abandoned {
+        game_recorder.add_abandoned();
     } else {
-        println!("FAILED {}", ans.to_uppercase());
+        game_recorder.add_game(outcome == Outcome::Won, guess_results.history.len() as u32);
+    }
+    if args.state.is_some() {
+        game_data.add_game(
+            args,
+            guess_results,
+            ans,
+            difficult,
+            outcome == Outcome::Abandoned,
+        );
     }
 }
 
 pub fn load_game(
-    args: &mut Args,
+    args: &Args,
     game_recorder: &mut GameRecorder,
     game_data: &mut GameData,
 ) -> Result<(), std::io::Error> {
-    if args.state.is_some() {
-        if let Result::Ok(data_file) = File::open(args.state.as_ref().unwrap()) {
-            *game_data = serde_json::from_reader(BufReader::new(data_file))?;
+    if let Some(state) = &args.state {
+        // an empty file was just created by a session saving to it for the first time
+        if let Result::Ok(content) = std::fs::read_to_string(state)
+            && !content.trim().is_empty()
+        {
+            *game_data = serde_json::from_str(&content)?;
+            game_data.saved_games = game_data.games.len();
+            game_data.saved_rounds = game_data.total_rounds;
         } // else: no such file, ignore, and use a empty game data
 
-        for SingleGameData {
-            answer: game_answer,
-            guesses: game_guesses,
-        } in &game_data.games
-        {
-            let is_game_win = game_answer == game_guesses.last().unwrap();
-            game_recorder.add_game(is_game_win, game_guesses.len() as u32);
-            for one_guess in game_guesses {
+        for game in &game_data.games {
+            if game.abandoned {
+                game_recorder.add_abandoned();
+            } else {
+                game_recorder.add_game(game.is_win(), game.attempts() as u32);
+            }
+            for one_guess in &game.guesses {
                 game_recorder.add_tried_word(one_guess.clone().to_lowercase());
             }
         }
     }
 
-    // load config
-    if let Some(config_path) = args.config.as_ref() {
-        let config_file = File::open(config_path)?;
-        let config: Config = serde_json::from_reader(BufReader::new(config_file))?;
+    Ok(())
+}
 
-        if args.word.is_none() {
-            args.word = config.word;
-        }
-        if args.random == false {
-            args.random = config.random;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::QueueInput;
+    use crate::render::JsonRenderer;
+    use clap::Parser;
+
+    fn queue(lines: &[&str]) -> QueueInput {
+        let mut input = QueueInput::default();
+        for line in lines {
+            input.push(line);
         }
-        if args.difficult == false {
-            args.difficult = config.difficult;
-        }
-        if args.stats == false {
-            args.stats = config.stats;
-        }
-        // day & seed
-        if args.word.is_none() {
-            if args.day.is_none() {
-                args.day = config.day;
-            } else {
-                args.day = Some(1);
-            }
+        input
+    }
 
-            if args.seed.is_none() {
-                args.seed = config.seed;
-            } // and if the config does not specify seed, use default value
-            if args.seed.is_none() {
-                args.seed = Some(114514);
-            }
-        }
-        if args.final_set.is_none() {
-            args.final_set = config.final_set;
-        }
-        if args.acceptable_set.is_none() {
-            args.acceptable_set = config.acceptable_set;
-        }
+    fn play(args: &[&str], lines: &[&str], ans: &str) -> (Vec<String>, Outcome) {
+        let args = Args::parse_from(args);
+        let acceptable = WordList::from_words(["crane", "grand", "slate", "boule"]).unwrap();
+        let mut input = queue(lines);
+        let mut made = Vec::new();
+        let (_, outcome) = play_one_game(
+            &JsonRenderer,
+            &mut input,
+            &args,
+            &acceptable,
+            ans,
+            &mut |guess_result| made.push(guess_result.content.clone()),
+        )
+        .unwrap();
+        (made, outcome)
     }
 
-    // even if there is no config file, a default seed must be specified
-    if args.random {
-        if args.seed.is_none() {
-            args.seed = Some(114514);
-        }
-        // so does the day
-        if args.day.is_none() {
-            args.day = Some(1);
-        }
+    #[test]
+    fn test_score_rejects_other_words() {
+        assert_eq!(score("grand", "crane").unwrap().to_string(), "RGGGR");
+        assert!(score("grand", "cranes").is_err());
+        assert!(score("grand", "cran").is_err());
+        assert!(score("grand", "CRANE").is_err());
+        assert!(score("grandiose", "grandiose").is_err());
     }
 
-    Ok(())
+    #[test]
+    fn test_invalid_guesses_are_skipped() {
+        let (made, outcome) = play(&["wordle"], &["zzzzz", "crane", "", "grand"], "grand");
+        assert_eq!(made, ["crane", "grand"]);
+        assert_eq!(outcome, Outcome::Won);
+    }
+
+    #[test]
+    fn test_difficult_mode_rejects_guess() {
+        // BOULE drops the A, R and N that CRANE found
+        let lines = ["crane", "boule", "grand"];
+        let (made, outcome) = play(&["wordle", "-D"], &lines, "grand");
+        assert_eq!(made, ["crane", "grand"]);
+        assert_eq!(outcome, Outcome::Won);
+        let (made, _) = play(&["wordle"], &lines, "grand");
+        assert_eq!(made, lines);
+    }
+
+    #[test]
+    fn test_lost_and_abandoned_games() {
+        let (made, outcome) = play(&["wordle"], &["crane"; 6], "grand");
+        assert_eq!(made.len(), 6);
+        assert_eq!(outcome, Outcome::Lost);
+        let (made, outcome) = play(&["wordle"], &["crane", "slate"], "grand");
+        assert_eq!(made, ["crane", "slate"]);
+        assert_eq!(outcome, Outcome::Abandoned);
+    }
+
+    /// Start a game with GRAND in progress after CRANE, returning the attempts of the
+    /// game and the game left in progress
+    fn start_with_unfinished(
+        args: &[&str],
+        lines: &[&str],
+        interactive: bool,
+    ) -> (f64, Option<UnfinishedGame>) {
+        let args = Args::parse_from(args);
+        let words = WordList::from_words(["crane", "grand", "slate", "boule"]).unwrap();
+        let mut input = queue(lines);
+        input.interactive = interactive;
+        let mut game_recorder = GameRecorder::new();
+        let mut game_data = GameData::new();
+        game_data.unfinished = Some(UnfinishedGame {
+            answer: "GRAND".to_string(),
+            difficult: false,
+            guesses: vec!["CRANE".to_string()],
+        });
+        let outcome = start_one_game(
+            &JsonRenderer,
+            &mut input,
+            &args,
+            &mut game_recorder,
+            &words,
+            &words,
+            &mut game_data,
+        )
+        .unwrap();
+        assert_eq!(outcome, Some(Outcome::Won));
+        (game_recorder.average_attempts(), game_data.unfinished)
+    }
+
+    #[test]
+    fn test_player_is_asked_to_resume() {
+        let (attempts, _) =
+            start_with_unfinished(&["wordle", "-w", "boule"], &["Y", "grand"], true);
+        assert_eq!(attempts, 2.0);
+        let (attempts, unfinished) =
+            start_with_unfinished(&["wordle", "-w", "boule"], &["N", "boule"], true);
+        assert_eq!(attempts, 1.0);
+        assert_eq!(unfinished, None);
+    }
+
+    #[test]
+    fn test_script_resumes_only_the_same_answer() {
+        let (attempts, _) = start_with_unfinished(&["wordle", "-w", "grand"], &["grand"], false);
+        assert_eq!(attempts, 2.0);
+        // the first line is a guess, not an answer to a question
+        let (attempts, unfinished) =
+            start_with_unfinished(&["wordle", "-w", "boule"], &["boule"], false);
+        assert_eq!(attempts, 1.0);
+        assert_eq!(unfinished.unwrap().guesses, ["CRANE"]);
+    }
 }
@@ -0,0 +1,234 @@
+use crate::args::{Args, ImportArgs};
+use crate::feedback::Feedback;
+use crate::game::{MAX_ATTEMPTS, MAX_WORD_LENGTH};
+use crate::messages;
+use crate::recorder::{GameData, SingleGameData};
+use std::path::Path;
+
+/// The header of a shared game
+struct Header {
+    day: Option<u32>,
+    /// The attempts of a won game, `None` for a lost one (`X/6`)
+    result: Option<usize>,
+    /// Marked by a `*` after the result
+    difficult: bool,
+}
+
+/// Parse a share header such as `Wordle 1,234 3/6*`
+fn parse_header(header: &str) -> Option<Header> {
+    let mut day = None;
+    let mut result = None;
+    let mut difficult = false;
+    for token in header.split_whitespace().skip(1) {
+        if let Some((attempts, max)) = token.split_once('/') {
+            result = match attempts {
+                "X" | "x" => Some(None),
+                attempts => Some(Some(attempts.parse().ok()?)),
+            };
+            difficult = max.ends_with('*');
+        } else if day.is_none() {
+            day = token.replace([',', '.'], "").parse().ok();
+        }
+    }
+    Some(Header {
+        day,
+        result: result?,
+        difficult,
+    })
+}
+
+/// A shared game being read, which only has the feedback of each guess
+struct SharedGame<'a> {
+    line: &'a str,
+    header: Header,
+    rows: Vec<Feedback>,
+}
+
+impl SharedGame<'_> {
+    fn finish(self) -> Result<SingleGameData, Box<dyn std::error::Error>> {
+        let (header, result) = (self.line, self.header.result);
+        let expected = result.unwrap_or(MAX_ATTEMPTS as usize);
+        if self.rows.len() != expected {
+            let found = self.rows.len();
+            return Err(format!("{header}: expected {expected} rows, found {found}").into());
+        }
+        if self.rows.last().is_some_and(|row| row.is_win()) != result.is_some() {
+            return Err(format!("{header}: the last row does not match the result").into());
+        }
+        Ok(SingleGameData {
+            feedback: self.rows.iter().map(|row| row.to_string()).collect(),
+            day: self.header.day,
+            difficult: self.header.difficult,
+            ..Default::default()
+        })
+    }
+}
+
+/// Parse results shared from the web version, several of them may follow each other:
+///
+/// ```text
+/// Wordle 1,234 3/6
+///
+/// ⬛🟨⬛⬛⬛
+/// 🟩🟩⬛🟨⬛
+/// 🟩🟩🟩🟩🟩
+/// ```
+pub fn parse_share(text: &str) -> Result<Vec<SingleGameData>, Box<dyn std::error::Error>> {
+    let mut games = Vec::new();
+    let mut current: Option<SharedGame> = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("Wordle") {
+            if let Some(game) = current.take() {
+                games.push(game.finish()?);
+            }
+            let header =
+                parse_header(line).ok_or_else(|| format!("Invalid share header {line}"))?;
+            current = Some(SharedGame {
+                line,
+                header,
+                rows: Vec::new(),
+            });
+        } else if let Some(game) = &mut current
+            && let Ok(row) = line.parse()
+        {
+            game.rows.push(row);
+        }
+        // anything else, e.g. a link or a comment, is ignored
+    }
+    if let Some(game) = current {
+        games.push(game.finish()?);
+    }
+    Ok(games)
+}
+
+/// Split a CSV line into its fields, which may be quoted
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Parse a CSV file with a header naming its columns. The `guesses` and `feedback`
+/// columns hold the words or the feedback of each guess separated by spaces; `answer`,
+/// `abandoned`, `mode` and `day` are optional and other columns are ignored, so the
+/// output of `export` can be imported again.
+pub fn parse_csv(text: &str) -> Result<Vec<SingleGameData>, Box<dyn std::error::Error>> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<String> = split_csv_line(header)
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| columns.iter().position(|column| column == name);
+    let (answer, guesses, feedback, abandoned, mode, day) = (
+        column("answer"),
+        column("guesses"),
+        column("feedback"),
+        column("abandoned"),
+        column("mode"),
+        column("day"),
+    );
+    if guesses.is_none() && feedback.is_none() {
+        return Err("The CSV file needs a guesses or a feedback column".into());
+    }
+
+    let mut games = Vec::new();
+    for (idx, line) in lines {
+        let fields = split_csv_line(line);
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column))
+                .map_or("", |field| field.trim())
+        };
+        let mut game = SingleGameData {
+            answer: field(answer).to_uppercase(),
+            guesses: field(guesses)
+                .split_whitespace()
+                .map(str::to_uppercase)
+                .collect(),
+            abandoned: field(abandoned).eq_ignore_ascii_case("true"),
+            difficult: field(mode).eq_ignore_ascii_case("difficult"),
+            ..Default::default()
+        };
+        // the answer and the guesses are words of one length
+        let length = game.guesses.first().unwrap_or(&game.answer).len();
+        let invalid = std::iter::once(&game.answer)
+            .filter(|answer| !answer.is_empty())
+            .chain(&game.guesses)
+            .find(|word| {
+                word.len() != length
+                    || length > MAX_WORD_LENGTH
+                    || !word.chars().all(|c| c.is_ascii_alphabetic())
+            });
+        if let Some(word) = invalid {
+            return Err(format!(
+                "Line {}: invalid word {word}, expected words of {length} letters",
+                idx + 1
+            )
+            .into());
+        }
+        for row in field(feedback).split_whitespace() {
+            let row: Feedback = row
+                .parse()
+                .map_err(|err| format!("Line {}: {err}", idx + 1))?;
+            game.feedback.push(row.to_string());
+        }
+        if !field(day).is_empty() {
+            let parsed = field(day).replace([',', '.'], "").parse();
+            game.day = Some(parsed.map_err(|_| format!("Line {}: invalid day", idx + 1))?);
+        }
+        if game.attempts() == 0 {
+            return Err(format!("Line {}: neither guesses nor feedback", idx + 1).into());
+        }
+        games.push(game);
+    }
+    Ok(games)
+}
+
+/// Add the games of every file to the state file, skipping those already in it
+pub fn run(
+    args: &Args,
+    import: &ImportArgs,
+    game_data: &mut GameData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut imported = 0;
+    let mut skipped = 0;
+    for path in &import.files {
+        let text = std::fs::read_to_string(path)?;
+        let is_csv = Path::new(path).extension().is_some_and(|ext| ext == "csv");
+        let games = if is_csv {
+            parse_csv(&text)
+        } else {
+            parse_share(&text)
+        };
+        for game in games.map_err(|err| format!("{path}: {err}"))? {
+            if game_data.games.contains(&game) {
+                skipped += 1;
+            } else {
+                game_data.games.push(game);
+                game_data.total_rounds += 1;
+                imported += 1;
+            }
+        }
+    }
+    game_data.save(args)?;
+    println!("{}", messages::get().imported(imported, skipped));
+    Ok(())
+}
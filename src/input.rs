@@ -0,0 +1,96 @@
+#[cfg(test)]
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::net::TcpStream;
+
+const SOCKET_PREFIX: &str = "tcp://";
+
+/// Where answers, guesses and other replies of the player come from
+pub trait Input {
+    /// The next line without its line ending, or `None` at the end of input
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+
+    /// Whether a player types the lines, who can be asked questions the lines
+    /// of a script or a file do not expect
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Read lines from any buffered reader, e.g. a file or a socket
+pub struct ReaderInput<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> ReaderInput<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: BufRead> Input for ReaderInput<R> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let len = line.trim_end_matches(['\r', '\n']).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+}
+
+pub struct StdinInput;
+
+impl Input for StdinInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        ReaderInput::new(io::stdin().lock()).read_line()
+    }
+
+    fn is_interactive(&self) -> bool {
+        atty::is(atty::Stream::Stdin)
+    }
+}
+
+/// Lines fed by the program itself, used by the tests of the game loop
+#[cfg(test)]
+#[derive(Default)]
+pub struct QueueInput {
+    lines: VecDeque<String>,
+    /// Pretend a player types the lines
+    pub interactive: bool,
+}
+
+#[cfg(test)]
+impl QueueInput {
+    pub fn push(&mut self, line: &str) {
+        self.lines.push_back(line.to_string());
+    }
+}
+
+#[cfg(test)]
+impl Input for QueueInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.lines.pop_front())
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+}
+
+/// Open an input source: `-` for stdin, `tcp://host:port` for a socket, or a file path
+pub fn open_input(source: &str) -> io::Result<Box<dyn Input>> {
+    if source == "-" {
+        Ok(Box::new(StdinInput))
+    } else if let Some(addr) = source.strip_prefix(SOCKET_PREFIX) {
+        Ok(Box::new(ReaderInput::new(BufReader::new(
+            TcpStream::connect(addr)?,
+        ))))
+    } else {
+        Ok(Box::new(ReaderInput::new(BufReader::new(File::open(
+            source,
+        )?))))
+    }
+}
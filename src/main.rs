@@ -1,34 +1,96 @@
 use clap::Parser;
-use std::io;
+mod analysis;
 mod args;
-use args::Args;
+mod assist;
+use args::{Args, Command};
 
 use crate::game::init_game;
-mod builtin_words;
+mod bot;
+mod cache;
+mod config;
+mod dictionaries;
+mod export;
+mod feedback;
 mod game;
+mod import;
+mod input;
+mod matrix;
+mod messages;
+mod race;
+mod rating;
 mod recorder;
+mod render;
+mod solver;
+mod state;
+mod theme;
+mod tournament;
+mod words;
 
 fn game_loop(
-    is_tty: bool,
+    renderer: &dyn render::Renderer,
+    input: &mut dyn input::Input,
     args: &mut Args,
     game_recorder: &mut recorder::GameRecorder,
     game_data: &mut recorder::GameData,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut final_words = Vec::<String>::new();
-    let mut acceptable = Vec::<String>::new();
+    let mut final_words = words::WordList::default();
+    let mut acceptable = words::WordList::default();
     init_game(args, &mut final_words, &mut acceptable)?;
-    loop {
-        game::start_one_game(
-            is_tty,
+
+    // A bot plays all its games in one go
+    if args.bot.is_some() {
+        bot::run_bot(
+            renderer,
             args,
             game_recorder,
             &final_words,
             &acceptable,
             game_data,
-        );
+        )?;
+        renderer.stats(game_recorder);
+        if args.state.is_some() {
+            game_data.save(args)?;
+        }
+        return Ok(());
+    }
+
+    // A race is a single game shared with other players
+    if args.host.is_some() || args.join.is_some() {
+        if args.host.is_some() {
+            race::host_race(
+                renderer,
+                input,
+                args,
+                game_recorder,
+                &final_words,
+                &acceptable,
+                game_data,
+            )?;
+        } else {
+            race::join_race(renderer, input, args, game_recorder, &acceptable, game_data)?;
+        }
+        if args.stats {
+            renderer.stats(game_recorder);
+        }
+        if args.state.is_some() {
+            game_data.save(args)?;
+        }
+        return Ok(());
+    }
+
+    // Play until the input ends before a game is started
+    while let Some(outcome) = game::start_one_game(
+        renderer,
+        input,
+        args,
+        game_recorder,
+        &final_words,
+        &acceptable,
+        game_data,
+    )? {
         // Show stats if requested
         if args.stats {
-            game_recorder.print();
+            renderer.stats(game_recorder);
         }
 
         // Day++
@@ -38,27 +100,28 @@ fn game_loop(
 
         // Save game data if requested
         if args.state.is_some() {
-            game_data.save(&args)?;
+            game_data.save(args)?;
         }
 
-        // Do not play again if word is specified
-        if args.word.is_some() {
+        // Do not play again if word is specified, or if the input ended
+        if args.word.is_some() || outcome == game::Outcome::Abandoned {
             break;
         }
 
+        if let Some(prompt) = messages::get().play_again() {
+            renderer.message(&prompt);
+        }
         let play_again = loop {
-            let mut buf = String::new();
-            let is_eof = io::stdin().read_line(&mut buf).expect("Invalid input!");
-            if is_eof == 0 {
+            let Some(buf) = input.read_line()? else {
                 break false;
-            }
-            buf = buf.trim().to_string();
+            };
+            let buf = buf.trim();
             if buf == "Y" {
                 break true;
             } else if buf == "N" {
                 break false;
             }
-            println!("Invalid input!");
+            renderer.message(&messages::get().invalid_input());
         };
 
         if !play_again {
@@ -73,6 +136,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // init
     let is_tty = atty::is(atty::Stream::Stdout);
     let mut args = Args::parse();
+    if let Some(Command::Play(play)) = &args.command
+        && play.format.is_some()
+    {
+        args.format = play.format;
+    }
     let mut game_recorder = recorder::GameRecorder::new();
     let mut game_data = recorder::GameData::new();
 
@@ -80,14 +148,68 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("{args:?}");
     }
 
-    game::load_game(&mut args, &mut game_recorder, &mut game_data)?;
+    let provenance = config::load_config(&mut args)?;
+    if args.print_config {
+        config::print_config(&args, &provenance);
+        return Ok(());
+    }
+    if args.list_dictionaries {
+        dictionaries::print_list();
+        return Ok(());
+    }
+    let renderer = render::new_renderer(&args, is_tty);
+    messages::init(args.lang.unwrap_or_default(), renderer.interactive());
+
+    match &args.command {
+        Some(Command::Tournament(tournament)) => return tournament::run(&args, tournament),
+        Some(Command::Solve(solve)) => return solver::run(&args, solve),
+        Some(Command::State(args::StateCommand::Merge(merge))) => return state::run_merge(merge),
+        Some(Command::Lists(lists)) => {
+            return match &lists.dictionary {
+                Some(name) => dictionaries::print_words(name, lists.acceptable),
+                None => {
+                    dictionaries::print_list();
+                    Ok(())
+                }
+            };
+        }
+        _ => {}
+    }
+
+    game::load_game(&args, &mut game_recorder, &mut game_data)?;
+    match &args.command {
+        Some(Command::Replay(replay)) => return analysis::replay(&args, replay, &game_data),
+        Some(Command::Stats | Command::Export(_) | Command::Import(_)) if args.state.is_none() => {
+            return Err("This command reads the games of a state file, given with --state".into());
+        }
+        Some(Command::Stats) => {
+            renderer.stats(&game_recorder);
+            return Ok(());
+        }
+        Some(Command::Export(export)) => return export::run(export, &game_data, &game_recorder),
+        Some(Command::Import(import)) => return import::run(&args, import, &mut game_data),
+        _ => {}
+    }
 
     if args.debug {
         println!("{args:?}");
         panic!();
     }
 
-    game_loop(is_tty, &mut args, &mut game_recorder, &mut game_data)?;
+    let mut input = input::open_input(args.input.as_deref().unwrap_or("-"))?;
+    if let Some(Command::Assist) = &args.command {
+        return assist::run(&args, input.as_mut());
+    }
+    if args.state.is_some() {
+        state::exit_on_interrupt()?;
+    }
+    game_loop(
+        renderer.as_ref(),
+        input.as_mut(),
+        &mut args,
+        &mut game_recorder,
+        &mut game_data,
+    )?;
 
     Ok(())
 }
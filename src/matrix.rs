@@ -0,0 +1,124 @@
+use crate::cache;
+use crate::feedback;
+use crate::game;
+use crate::words::WordList;
+use std::thread;
+
+/// The feedback of every acceptable word against every final word, as base 3 codes
+pub struct FeedbackMatrix {
+    guesses: WordList,
+    answers: WordList,
+    hash: u64,
+    /// Bytes of each code in little endian, 1 for words of up to 5 letters and 2 above
+    width: usize,
+    /// One row of answers for each guess
+    codes: Vec<u8>,
+}
+
+/// Sorted, so the matrix does not depend on the order of a list
+fn normalize(words: &WordList) -> WordList {
+    let mut words = words.clone();
+    words.sort();
+    words
+}
+
+/// Score every guess against every answer, splitting the guesses between threads
+fn compute(guesses: &WordList, answers: &WordList, width: usize) -> Vec<u8> {
+    let mut codes = vec![0; guesses.len() * answers.len() * width];
+    if answers.is_empty() {
+        return codes;
+    }
+    let guesses: Vec<&str> = guesses.iter().collect();
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let rows_per_thread = guesses.len().div_ceil(threads).max(1);
+    let row_size = answers.len() * width;
+    thread::scope(|scope| {
+        for (guesses, codes) in guesses
+            .chunks(rows_per_thread)
+            .zip(codes.chunks_mut(rows_per_thread * row_size))
+        {
+            scope.spawn(move || {
+                for (guess, row) in guesses.iter().zip(codes.chunks_mut(row_size)) {
+                    for (answer, code) in answers.iter().zip(row.chunks_mut(width)) {
+                        let feedback = game::score(answer, guess)
+                            .expect("the words of both lists have the same length");
+                        let bytes = feedback.encode().to_le_bytes();
+                        code.copy_from_slice(&bytes[..width]);
+                    }
+                }
+            });
+        }
+    });
+    codes
+}
+
+impl FeedbackMatrix {
+    /// The matrix of the given lists, read from the cache if it was computed before
+    pub fn new(acceptable: &WordList, final_words: &WordList) -> Self {
+        let guesses = normalize(acceptable);
+        let answers = normalize(final_words);
+        // an empty word separates the lists, so moving a word from one to the other changes the hash
+        let hash = cache::hash_words(guesses.iter().chain([""]).chain(answers.iter()));
+
+        // one file for each pair of lists, so switching lists does not overwrite the other matrix
+        let path =
+            cache::cache_dir().map(|dir| dir.join(format!("feedback_matrix.{hash:016x}.bin")));
+        let width = if feedback::feedbacks(answers.word_length()) <= 256 {
+            1
+        } else {
+            2
+        };
+        let size = guesses.len() * answers.len() * width;
+        let codes = match path.as_ref().and_then(|path| cache::load_bytes(path, hash)) {
+            Some(codes) if codes.len() == size => codes,
+            _ => {
+                let codes = compute(&guesses, &answers, width);
+                if let Some(path) = &path {
+                    cache::store_bytes(path, hash, &codes);
+                }
+                codes
+            }
+        };
+        Self {
+            guesses,
+            answers,
+            hash,
+            width,
+            codes,
+        }
+    }
+
+    pub fn guesses(&self) -> &WordList {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> &WordList {
+        &self.answers
+    }
+
+    /// A hash of both lists, to key data computed from them
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn guess_index(&self, word: &str) -> Option<usize> {
+        self.guesses.index_of(word)
+    }
+
+    /// The base 3 code of the feedback of a guess against an answer, both given by index
+    pub fn code(&self, guess: usize, answer: usize) -> u16 {
+        let idx = (guess * self.answers.len() + answer) * self.width;
+        match self.width {
+            1 => self.codes[idx].into(),
+            _ => u16::from_le_bytes([self.codes[idx], self.codes[idx + 1]]),
+        }
+    }
+
+    /// The code of any word against an answer, scoring it if it is not an acceptable word
+    pub fn code_of(&self, guess: &str, answer: usize) -> Result<u16, String> {
+        match self.guess_index(guess) {
+            Some(guess) => Ok(self.code(guess, answer)),
+            None => Ok(game::score(self.answers.get(answer), guess)?.encode()),
+        }
+    }
+}
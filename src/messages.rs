@@ -0,0 +1,471 @@
+use crate::game::CharStatus;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Languages of the user-facing messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Zh,
+}
+
+/// Locale variables checked for the default language, in order of precedence
+const LOCALE_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// The language of the first locale variable that is set, and its name
+pub fn locale_lang() -> Option<(String, Lang)> {
+    LOCALE_VARS.iter().find_map(|var| {
+        let locale = std::env::var(var)
+            .ok()
+            .filter(|locale| !locale.is_empty())?;
+        let lang = if locale.starts_with("zh") {
+            Lang::Zh
+        } else {
+            Lang::En
+        };
+        Some((var.to_string(), lang))
+    })
+}
+
+/// The message catalogue. Without a tty the plain protocol strings are used in every
+/// language, so that the output stays machine readable.
+pub struct Messages {
+    lang: Lang,
+    is_tty: bool,
+}
+
+static MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+/// Select the catalogue once at start up
+pub fn init(lang: Lang, is_tty: bool) {
+    let _ = MESSAGES.set(Messages { lang, is_tty });
+}
+
+/// The selected catalogue, or the protocol strings if none is selected yet
+pub fn get() -> &'static Messages {
+    MESSAGES.get_or_init(|| Messages {
+        lang: Lang::En,
+        is_tty: false,
+    })
+}
+
+impl Messages {
+    /// An answer or guess that is not accepted
+    pub fn invalid(&self) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => "INVALID".to_string(),
+            (true, Lang::En) => "Invalid word, please try again.".to_string(),
+            (true, Lang::Zh) => "无效的单词，请重试。".to_string(),
+        }
+    }
+
+    pub fn correct(&self, attempts: u32) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => format!("CORRECT {attempts}"),
+            (true, Lang::En) if attempts == 1 => "Correct! You found it in 1 guess.".to_string(),
+            (true, Lang::En) => format!("Correct! You found it in {attempts} guesses."),
+            (true, Lang::Zh) => format!("正确！你用了 {attempts} 次猜出了答案。"),
+        }
+    }
+
+    pub fn failed(&self, answer: &str) -> String {
+        let answer = answer.to_uppercase();
+        match (self.is_tty, self.lang) {
+            (false, _) => format!("FAILED {answer}"),
+            (true, Lang::En) => format!("Failed! The answer was {answer}."),
+            (true, Lang::Zh) => format!("失败！答案是 {answer}。"),
+        }
+    }
+
+    /// The input ended before the game was over
+    pub fn abandoned(&self, answer: &str) -> String {
+        let answer = answer.to_uppercase();
+        match (self.is_tty, self.lang) {
+            (false, _) => format!("ABANDONED {answer}"),
+            (true, Lang::En) => format!("Game abandoned. The answer was {answer}."),
+            (true, Lang::Zh) => format!("已放弃本局。答案是 {answer}。"),
+        }
+    }
+
+    /// Abandoned games are only shown on a tty, and only if there are some
+    pub fn stats(&self, win: u32, lose: u32, abandoned: u32, average_attempts: f64) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => format!("{win} {lose} {average_attempts:.2}"),
+            (true, Lang::En) if abandoned > 0 => format!(
+                "Won {win}, lost {lose}, abandoned {abandoned}, average attempts {average_attempts:.2}"
+            ),
+            (true, Lang::En) => {
+                format!("Won {win}, lost {lose}, average attempts {average_attempts:.2}")
+            }
+            (true, Lang::Zh) if abandoned > 0 => format!(
+                "胜 {win} 局，负 {lose} 局，放弃 {abandoned} 局，平均猜测次数 {average_attempts:.2}"
+            ),
+            (true, Lang::Zh) => {
+                format!("胜 {win} 局，负 {lose} 局，平均猜测次数 {average_attempts:.2}")
+            }
+        }
+    }
+
+    /// `words` is the list of words and counts, e.g. `CRANE 2 AUDIO 1`
+    pub fn top_words(&self, words: &str) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => words.to_string(),
+            (true, Lang::En) => format!("Most tried words: {words}"),
+            (true, Lang::Zh) => format!("最常猜的单词：{words}"),
+        }
+    }
+
+    /// Asked before every new round, only shown on a tty
+    pub fn play_again(&self) -> Option<String> {
+        match (self.is_tty, self.lang) {
+            (false, _) => None,
+            (true, Lang::En) => Some("Play again? [Y/N]".to_string()),
+            (true, Lang::Zh) => Some("再玩一局？[Y/N]".to_string()),
+        }
+    }
+
+    /// An answer to the play again question that is neither `Y` nor `N`
+    pub fn invalid_input(&self) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => "Invalid input!".to_string(),
+            (true, Lang::En) => "Invalid input, please enter Y or N.".to_string(),
+            (true, Lang::Zh) => "输入无效，请输入 Y 或 N。".to_string(),
+        }
+    }
+
+    /// Asked when the state file has a game that was interrupted, only shown on a tty
+    pub fn resume_prompt(&self, guesses: usize) -> Option<String> {
+        match (self.is_tty, self.lang) {
+            (false, _) => None,
+            (true, Lang::En) => Some(format!(
+                "Resume the unfinished game, {guesses} guesses made? [Y/N]"
+            )),
+            (true, Lang::Zh) => Some(format!("继续未完成的一局（已猜 {guesses} 次）？[Y/N]")),
+        }
+    }
+
+    /// Printed to stderr when Ctrl-C ends a game whose progress is saved
+    pub fn interrupted(&self) -> String {
+        match self.lang {
+            Lang::En => "Interrupted, the unfinished game is saved in the state file.".to_string(),
+            Lang::Zh => "已中断，未完成的一局已保存到状态文件。".to_string(),
+        }
+    }
+
+    /// Printed to stderr when `--no-repeat` finds no answer left to play
+    pub fn answers_exhausted(&self) -> String {
+        match self.lang {
+            Lang::En => {
+                "Every answer has been played, answers will repeat from now on.".to_string()
+            }
+            Lang::Zh => "所有答案都已玩过，之后的答案将会重复。".to_string(),
+        }
+    }
+
+    /// Only shown on a tty
+    pub fn race_joined(&self, name: &str) -> Option<String> {
+        match (self.is_tty, self.lang) {
+            (false, _) => None,
+            (true, Lang::En) => Some(format!(
+                "Joined as {name}, waiting for the race to start..."
+            )),
+            (true, Lang::Zh) => Some(format!("已作为 {name} 加入，等待比赛开始……")),
+        }
+    }
+
+    pub fn race_winner(&self, name: &str, attempts: u32) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => format!("WINNER {name} {attempts}"),
+            (true, Lang::En) => format!("{name} wins the race in {attempts} guesses!"),
+            (true, Lang::Zh) => format!("{name} 用 {attempts} 次猜测赢得了比赛！"),
+        }
+    }
+
+    pub fn race_no_winner(&self) -> String {
+        match (self.is_tty, self.lang) {
+            (false, _) => "NO WINNER".to_string(),
+            (true, Lang::En) => "Nobody solved the puzzle.".to_string(),
+            (true, Lang::Zh) => "没有人猜出答案。".to_string(),
+        }
+    }
+
+    /// How the screen reader renderer describes the status of a letter
+    pub fn reader_status(&self, status: CharStatus) -> &'static str {
+        match (self.lang, status) {
+            (Lang::En, CharStatus::Correct) => "correct",
+            (Lang::En, CharStatus::WrongPosition) => "in the wrong place",
+            (Lang::En, CharStatus::TooMany) => "not in the word",
+            (Lang::En, CharStatus::Unknown) => "unknown",
+            (Lang::Zh, CharStatus::Correct) => "正确",
+            (Lang::Zh, CharStatus::WrongPosition) => "位置错误",
+            (Lang::Zh, CharStatus::TooMany) => "不在单词中",
+            (Lang::Zh, CharStatus::Unknown) => "未知",
+        }
+    }
+
+    /// A list read out by the screen reader renderer, e.g. `A, E`
+    pub fn reader_list(&self, items: &[String]) -> String {
+        match (self.lang, items.is_empty()) {
+            (Lang::En, true) => "none".to_string(),
+            (Lang::En, false) => items.join(", "),
+            (Lang::Zh, true) => "无".to_string(),
+            (Lang::Zh, false) => items.join("、"),
+        }
+    }
+
+    pub fn reader_guess(&self, attempt: usize, letters: &str) -> String {
+        match self.lang {
+            Lang::En => format!("Guess {attempt}: {letters}."),
+            Lang::Zh => format!("第 {attempt} 次猜测：{letters}。"),
+        }
+    }
+
+    pub fn reader_keyboard(&self, correct: &str, wrong_place: &str, absent: &str) -> String {
+        match self.lang {
+            Lang::En => format!(
+                "Correct letters: {correct}. Wrong place: {wrong_place}. Not in the word: {absent}."
+            ),
+            Lang::Zh => {
+                format!("正确的字母：{correct}。位置错误：{wrong_place}。不在单词中：{absent}。")
+            }
+        }
+    }
+
+    pub fn reader_opponent(&self, name: &str, correct: usize, wrong_place: usize) -> String {
+        match self.lang {
+            Lang::En => {
+                format!("{name} guessed: {correct} correct, {wrong_place} in the wrong place.")
+            }
+            Lang::Zh => format!("{name} 猜测了：{correct} 个正确，{wrong_place} 个位置错误。"),
+        }
+    }
+
+    /// The heading of the leaderboard of one mode of a tournament
+    pub fn tournament_mode(&self, difficult: bool, answers: usize) -> String {
+        match (self.lang, difficult) {
+            (Lang::En, false) => format!("normal mode, {answers} answers"),
+            (Lang::En, true) => format!("difficult mode, {answers} answers"),
+            (Lang::Zh, false) => format!("普通模式，{answers} 个答案"),
+            (Lang::Zh, true) => format!("困难模式，{answers} 个答案"),
+        }
+    }
+
+    pub fn tournament_columns(&self) -> String {
+        match self.lang {
+            Lang::En => "rank   mean failures strategy".to_string(),
+            Lang::Zh => "名次   平均     失败 策略".to_string(),
+        }
+    }
+
+    pub fn head_to_head(&self) -> String {
+        match self.lang {
+            Lang::En => {
+                "head-to-head, wins/ties/losses of each row against each column".to_string()
+            }
+            Lang::Zh => "两两对比，每行对每列的胜/平/负".to_string(),
+        }
+    }
+
+    pub fn analysis_columns(&self) -> String {
+        match self.lang {
+            Lang::En => "guess  before  after  expected  best guess      skill  luck".to_string(),
+            Lang::Zh => "猜测   之前    之后   期望剩余  最佳猜测        技巧   运气".to_string(),
+        }
+    }
+
+    /// The note explaining the `*` marking guesses that could not be the answer
+    pub fn analysis_inconsistent(&self) -> String {
+        match self.lang {
+            Lang::En => "* not possible given the feedback of the earlier guesses".to_string(),
+            Lang::Zh => "* 与之前猜测的反馈不符".to_string(),
+        }
+    }
+
+    /// The heading of a game analysed by `replay`
+    pub fn replay_game(&self, number: usize, answer: &str) -> String {
+        let answer = answer.to_uppercase();
+        match self.lang {
+            Lang::En => format!("Game {number}: {answer}"),
+            Lang::Zh => format!("第 {number} 局：{answer}"),
+        }
+    }
+
+    /// Printed to stderr for a game whose words do not fit the word lists
+    pub fn replay_skipped(&self, number: usize) -> String {
+        match self.lang {
+            Lang::En => format!("Game {number} is skipped, its words do not fit the word lists."),
+            Lang::Zh => format!("已跳过第 {number} 局，其单词与词表不符。"),
+        }
+    }
+
+    pub fn assist_intro(&self) -> String {
+        match self.lang {
+            Lang::En => "Enter each guess and its feedback, e.g. CRANE GYXXG".to_string(),
+            Lang::Zh => "请输入每次的猜测及其反馈，例如 CRANE GYXXG".to_string(),
+        }
+    }
+
+    /// `listed` names the candidates when there are few of them
+    pub fn assist_candidates(&self, count: usize, listed: Option<&str>) -> String {
+        match (self.lang, listed) {
+            (Lang::En, Some(listed)) => format!("Candidates: {count} ({listed})"),
+            (Lang::En, None) => format!("Candidates: {count}"),
+            (Lang::Zh, Some(listed)) => format!("候选词：{count} 个（{listed}）"),
+            (Lang::Zh, None) => format!("候选词：{count} 个"),
+        }
+    }
+
+    pub fn assist_suggestion(&self, guess: &str, expected: f64) -> String {
+        let guess = guess.to_uppercase();
+        match self.lang {
+            Lang::En => format!("Suggested guess: {guess} (about {expected:.1} candidates left)"),
+            Lang::Zh => format!("建议猜测：{guess}（预计剩余约 {expected:.1} 个候选词）"),
+        }
+    }
+
+    pub fn assist_invalid_row(&self) -> String {
+        match self.lang {
+            Lang::En => "Invalid row, enter a guess and its feedback, e.g. CRANE GYXXG".to_string(),
+            Lang::Zh => "无效的输入，请输入猜测及其反馈，例如 CRANE GYXXG".to_string(),
+        }
+    }
+
+    pub fn assist_solved(&self, guess: &str) -> String {
+        let guess = guess.to_uppercase();
+        match self.lang {
+            Lang::En => format!("Solved: {guess}"),
+            Lang::Zh => format!("已解出：{guess}"),
+        }
+    }
+
+    pub fn assist_no_match(&self) -> String {
+        match self.lang {
+            Lang::En => "No word matches this feedback, please check it and try again".to_string(),
+            Lang::Zh => "没有单词符合这个反馈，请检查后重试".to_string(),
+        }
+    }
+
+    /// The result of `solve`
+    pub fn solved(&self, attempts: usize) -> String {
+        match self.lang {
+            Lang::En => format!("Solved in {attempts}"),
+            Lang::Zh => format!("用 {attempts} 次解出"),
+        }
+    }
+
+    pub fn not_solved(&self, answer: &str) -> String {
+        let answer = answer.to_uppercase();
+        match self.lang {
+            Lang::En => format!("Not solved: {answer}"),
+            Lang::Zh => format!("未能解出：{answer}"),
+        }
+    }
+
+    /// The title of the Markdown report written by `export`
+    pub fn report_title(&self) -> String {
+        match self.lang {
+            Lang::En => "Wordle results".to_string(),
+            Lang::Zh => "Wordle 成绩".to_string(),
+        }
+    }
+
+    /// Games, won, lost, win rate and average attempts
+    pub fn report_summary_columns(&self) -> [&'static str; 6] {
+        match self.lang {
+            Lang::En => [
+                "Games",
+                "Won",
+                "Lost",
+                "Abandoned",
+                "Win rate",
+                "Average attempts",
+            ],
+            Lang::Zh => ["局数", "胜", "负", "放弃", "胜率", "平均猜测次数"],
+        }
+    }
+
+    pub fn report_distribution(&self) -> String {
+        match self.lang {
+            Lang::En => "Guess distribution".to_string(),
+            Lang::Zh => "猜测次数分布".to_string(),
+        }
+    }
+
+    /// Attempts and games
+    pub fn report_distribution_columns(&self) -> [&'static str; 2] {
+        match self.lang {
+            Lang::En => ["Attempts", "Games"],
+            Lang::Zh => ["猜测次数", "局数"],
+        }
+    }
+
+    pub fn report_top_words(&self) -> String {
+        match self.lang {
+            Lang::En => "Most tried words".to_string(),
+            Lang::Zh => "最常猜的单词".to_string(),
+        }
+    }
+
+    /// Word and times
+    pub fn report_top_words_columns(&self) -> [&'static str; 2] {
+        match self.lang {
+            Lang::En => ["Word", "Times"],
+            Lang::Zh => ["单词", "次数"],
+        }
+    }
+
+    /// The summary of `import`
+    pub fn imported(&self, imported: usize, skipped: usize) -> String {
+        match self.lang {
+            Lang::En => {
+                format!("Imported {imported} games, skipped {skipped} already in the state file")
+            }
+            Lang::Zh => format!("导入了 {imported} 局，跳过了 {skipped} 局已在状态文件中的游戏"),
+        }
+    }
+
+    /// The summary of `state merge`
+    pub fn merged(&self, games: usize, files: usize, output: &str) -> String {
+        match self.lang {
+            Lang::En => format!("Merged {games} games from {files} files into {output}"),
+            Lang::Zh => format!("已将 {files} 个文件中的 {games} 局合并到 {output}"),
+        }
+    }
+
+    /// The name of an imported game whose answer is unknown, e.g. `day 1234`
+    pub fn game_of_day(&self, day: Option<u32>) -> String {
+        match (self.lang, day) {
+            (Lang::En, Some(day)) => format!("day {day}"),
+            (Lang::En, None) => "unknown answer".to_string(),
+            (Lang::Zh, Some(day)) => format!("第 {day} 天"),
+            (Lang::Zh, None) => "未知答案".to_string(),
+        }
+    }
+
+    /// The same game is found a different number of times in each file, `found`
+    /// giving the files and how many times each has it
+    pub fn count_conflict(&self, game: &str, found: &[(&str, usize)], kept: usize) -> String {
+        let found: Vec<String> = found
+            .iter()
+            .map(|(file, count)| match self.lang {
+                Lang::En => format!("{count} in {file}"),
+                Lang::Zh => format!("{file} 中 {count} 局"),
+            })
+            .collect();
+        match self.lang {
+            Lang::En => format!("Conflict: {game}: {}, kept {kept}", found.join(", ")),
+            Lang::Zh => format!("冲突：{game}：{}，保留 {kept} 局", found.join("，")),
+        }
+    }
+
+    /// Different games of the same daily puzzle, `plays` listing their rows
+    pub fn day_conflict(&self, day: u32, plays: &str) -> String {
+        match self.lang {
+            Lang::En => format!("Conflict: day {day} was played differently: {plays}"),
+            Lang::Zh => format!("冲突：第 {day} 天有不同的玩法：{plays}"),
+        }
+    }
+}
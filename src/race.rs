@@ -0,0 +1,340 @@
+use crate::args::Args;
+use crate::feedback::Feedback;
+use crate::game::{self, GuessResult, Outcome};
+use crate::input::Input;
+use crate::messages;
+use crate::recorder::{GameData, GameRecorder};
+use crate::render::Renderer;
+use crate::words::WordList;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const CONNECT_RETRIES: u32 = 50;
+const CONNECT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Messages shown to a player while racing
+enum RaceEvent {
+    Progress(String, String),
+    Finished,
+    Winner(Option<(String, u32)>),
+    Closed,
+}
+
+/// A finished player, in the order they finished
+struct RaceResult {
+    name: String,
+    win: bool,
+    attempts: u32,
+}
+
+type Writers = Arc<Mutex<Vec<(String, TcpStream)>>>;
+
+/// Send a line to every connected player except `from`
+fn broadcast(writers: &Writers, from: &str, line: &str) {
+    for (name, stream) in writers.lock().unwrap().iter_mut() {
+        if name != from {
+            // a dropped player is noticed by its reader thread
+            let _ = writeln!(stream, "{line}");
+        }
+    }
+}
+
+/// Show an opponent's row, parsed from its `G/Y/R/X` codes
+fn print_progress(renderer: &dyn Renderer, name: &str, codes: &str) {
+    if let Ok(feedback) = codes.parse::<Feedback>() {
+        renderer.opponent(name, feedback.status());
+    }
+}
+
+fn print_winner(renderer: &dyn Renderer, winner: &Option<(String, u32)>) {
+    renderer.message(&match winner {
+        Some((name, attempts)) => messages::get().race_winner(name, *attempts),
+        None => messages::get().race_no_winner(),
+    });
+}
+
+/// Print everything opponents did since the last call, without blocking
+fn drain_events(renderer: &dyn Renderer, events: &Receiver<RaceEvent>) {
+    while let Ok(event) = events.try_recv() {
+        if let RaceEvent::Progress(name, codes) = event {
+            print_progress(renderer, &name, &codes);
+        }
+    }
+}
+
+/// Read `GUESS`/`DONE` lines from one player and relay them to the others
+fn serve_player(
+    name: String,
+    reader: BufReader<TcpStream>,
+    writers: Writers,
+    results: Arc<Mutex<Vec<RaceResult>>>,
+    events: Sender<RaceEvent>,
+) {
+    let mut finished = false;
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["GUESS", codes] => {
+                broadcast(&writers, &name, &format!("PROGRESS {name} {codes}"));
+                let _ = events.send(RaceEvent::Progress(name.clone(), codes.to_string()));
+            }
+            ["DONE", win, attempts] => {
+                let win = *win == "1";
+                let attempts = attempts.parse().unwrap_or(0);
+                results.lock().unwrap().push(RaceResult {
+                    name: name.clone(),
+                    win,
+                    attempts,
+                });
+                finished = true;
+                let _ = events.send(RaceEvent::Finished);
+                break;
+            }
+            _ => (),
+        }
+    }
+    // a player leaving early loses the race
+    if !finished {
+        results.lock().unwrap().push(RaceResult {
+            name,
+            win: false,
+            attempts: 0,
+        });
+        let _ = events.send(RaceEvent::Finished);
+    }
+}
+
+/// Host a race: wait for the players, pick the answer, play, and declare the winner
+pub fn host_race(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    args: &Args,
+    game_recorder: &mut GameRecorder,
+    final_words: &WordList,
+    acceptable: &WordList,
+    game_data: &mut GameData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(args.host.as_ref().unwrap())?;
+    let host_name = args.name.clone().unwrap_or("host".to_string());
+    let writers: Writers = Arc::new(Mutex::new(Vec::new()));
+    let mut readers = Vec::new();
+    let mut names = vec![host_name.clone()];
+
+    // wait for every player to join
+    while readers.len() < args.players.unwrap_or(1) {
+        let (mut stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let Some(requested) = line.trim().strip_prefix("JOIN") else {
+            continue;
+        };
+        let requested = match requested.trim() {
+            "" => format!("player{}", names.len()),
+            requested => requested.to_string(),
+        };
+        let mut name = requested.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+            name = format!("{requested}{suffix}");
+            suffix += 1;
+        }
+        writeln!(stream, "WELCOME {name}")?;
+        names.push(name.clone());
+        writers.lock().unwrap().push((name.clone(), stream));
+        readers.push((name, reader));
+    }
+
+    let Some(ans) = game::gen_answer(renderer, input, args, final_words, game_data)? else {
+        return Err("The input ended before an answer was given!".into());
+    };
+    broadcast(
+        &writers,
+        &host_name,
+        &format!("START {ans} {}", args.difficult as u8),
+    );
+
+    let results = Arc::new(Mutex::new(Vec::<RaceResult>::new()));
+    let (sender, events) = mpsc::channel();
+    for (name, reader) in readers {
+        let writers = Arc::clone(&writers);
+        let results = Arc::clone(&results);
+        let sender = sender.clone();
+        thread::spawn(move || serve_player(name, reader, writers, results, sender));
+    }
+    drop(sender);
+
+    let (guess_results, outcome) = game::play_one_game(
+        renderer,
+        input,
+        args,
+        acceptable,
+        &ans,
+        &mut |guess_result: &GuessResult| {
+            let codes = guess_result.codes();
+            broadcast(
+                &writers,
+                &host_name,
+                &format!("PROGRESS {host_name} {codes}"),
+            );
+            drain_events(renderer, &events);
+        },
+    )?;
+    game::record_game(
+        args,
+        game_recorder,
+        game_data,
+        &guess_results,
+        &ans,
+        args.difficult,
+        outcome,
+    );
+    let win = outcome == Outcome::Won;
+    results.lock().unwrap().push(RaceResult {
+        name: host_name.clone(),
+        win,
+        attempts: guess_results.history.len() as u32,
+    });
+
+    // wait for the others to finish
+    while results.lock().unwrap().len() < names.len() {
+        match events.recv() {
+            Ok(RaceEvent::Progress(name, codes)) => print_progress(renderer, &name, &codes),
+            Ok(_) => (),
+            Err(_) => break,
+        }
+    }
+
+    // fewest attempts wins, ties go to whoever finished first
+    let winner = results
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|result| result.win)
+        .min_by_key(|result| result.attempts)
+        .map(|result| (result.name.clone(), result.attempts));
+    match &winner {
+        Some((name, attempts)) => {
+            broadcast(&writers, &host_name, &format!("WINNER {name} {attempts}"))
+        }
+        None => broadcast(&writers, &host_name, "WINNER -"),
+    }
+    print_winner(renderer, &winner);
+    Ok(())
+}
+
+/// Read `PROGRESS`/`WINNER` lines from the host
+fn listen_host(reader: BufReader<TcpStream>, events: Sender<RaceEvent>) {
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["PROGRESS", name, codes] => {
+                let _ = events.send(RaceEvent::Progress(name.to_string(), codes.to_string()));
+            }
+            ["WINNER", "-"] => {
+                let _ = events.send(RaceEvent::Winner(None));
+            }
+            ["WINNER", name, attempts] => {
+                let attempts = attempts.parse().unwrap_or(0);
+                let _ = events.send(RaceEvent::Winner(Some((name.to_string(), attempts))));
+            }
+            _ => (),
+        }
+    }
+    let _ = events.send(RaceEvent::Closed);
+}
+
+fn connect_with_retry(addr: &str) -> std::io::Result<TcpStream> {
+    let mut tries = 1;
+    loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => return Ok(stream),
+            Err(_) if tries < CONNECT_RETRIES => {
+                tries += 1;
+                thread::sleep(CONNECT_INTERVAL);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Join a race: receive the answer from the host, play, and wait for the winner
+pub fn join_race(
+    renderer: &dyn Renderer,
+    input: &mut dyn Input,
+    args: &mut Args,
+    game_recorder: &mut GameRecorder,
+    acceptable: &WordList,
+    game_data: &mut GameData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut stream = connect_with_retry(args.join.as_ref().unwrap())?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    writeln!(stream, "JOIN {}", args.name.clone().unwrap_or_default())?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let Some(name) = line.trim().strip_prefix("WELCOME ") else {
+        return Err("Unexpected reply from the race host!".into());
+    };
+    if let Some(joined) = messages::get().race_joined(name) {
+        renderer.message(&joined);
+    }
+
+    line.clear();
+    reader.read_line(&mut line)?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let ans = match parts.as_slice() {
+        ["START", ans, difficult] => {
+            args.difficult = *difficult == "1";
+            ans.to_string()
+        }
+        _ => return Err("The race host closed the connection!".into()),
+    };
+
+    let (sender, events) = mpsc::channel();
+    thread::spawn(move || listen_host(reader, sender));
+
+    let (guess_results, outcome) = game::play_one_game(
+        renderer,
+        input,
+        args,
+        acceptable,
+        &ans,
+        &mut |guess_result: &GuessResult| {
+            let _ = writeln!(stream, "GUESS {}", guess_result.codes());
+            drain_events(renderer, &events);
+        },
+    )?;
+    game::record_game(
+        args,
+        game_recorder,
+        game_data,
+        &guess_results,
+        &ans,
+        args.difficult,
+        outcome,
+    );
+    let win = outcome == Outcome::Won;
+    writeln!(stream, "DONE {} {}", win as u8, guess_results.history.len())?;
+
+    loop {
+        match events.recv() {
+            Ok(RaceEvent::Progress(name, codes)) => print_progress(renderer, &name, &codes),
+            Ok(RaceEvent::Winner(winner)) => {
+                print_winner(renderer, &winner);
+                break;
+            }
+            Ok(RaceEvent::Finished) => (),
+            Ok(RaceEvent::Closed) | Err(_) => {
+                return Err("The race host closed the connection!".into());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::args::Args;
+use crate::cache;
+use crate::words::WordList;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Bands of answers, each holding a third of the final list
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+/// How hard an answer is to find, the higher the score the harder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rating {
+    pub score: f64,
+    pub difficulty: Difficulty,
+    /// Final words differing in a single position, e.g. LIGHT and NIGHT
+    pub neighbours: usize,
+}
+
+/// The score of each reason making a word harder
+const RARITY_WEIGHT: f64 = 1.0;
+const REPEAT_WEIGHT: f64 = 1.0;
+const NEIGHBOUR_WEIGHT: f64 = 0.5;
+
+/// Rate every word from its letter rarity, repeated letters and near neighbours
+pub fn rate(final_words: &WordList) -> BTreeMap<String, Rating> {
+    // the share of words containing each letter
+    let mut containing: HashMap<char, usize> = HashMap::new();
+    for word in final_words.iter() {
+        let mut letters: Vec<char> = word.chars().collect();
+        letters.sort_unstable();
+        letters.dedup();
+        for letter in letters {
+            *containing.entry(letter).or_insert(0) += 1;
+        }
+    }
+    let share = |letter: char| containing[&letter] as f64 / final_words.len() as f64;
+
+    let mut scored: Vec<(&str, f64, usize)> = final_words
+        .iter()
+        .map(|word| {
+            let mut letters: Vec<char> = word.chars().collect();
+            letters.sort_unstable();
+            let length = letters.len();
+            letters.dedup();
+            let rarity: f64 = letters.iter().map(|letter| 1.0 - share(*letter)).sum();
+            let repeats = length - letters.len();
+            let neighbours = final_words
+                .iter()
+                .filter(|other| {
+                    other
+                        .chars()
+                        .zip(word.chars())
+                        .filter(|(a, b)| a != b)
+                        .count()
+                        == 1
+                })
+                .count();
+            let score = RARITY_WEIGHT * rarity
+                + REPEAT_WEIGHT * repeats as f64
+                + NEIGHBOUR_WEIGHT * neighbours as f64;
+            (word, score, neighbours)
+        })
+        .collect();
+
+    // split the words sorted by score into thirds
+    scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let count = scored.len();
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (word, score, neighbours))| {
+            let difficulty = match rank * 3 / count {
+                0 => Difficulty::Easy,
+                1 => Difficulty::Medium,
+                _ => Difficulty::Hard,
+            };
+            let rating = Rating {
+                score,
+                difficulty,
+                neighbours,
+            };
+            (word.to_string(), rating)
+        })
+        .collect()
+}
+
+/// The ratings are cached next to the final list file, or in the cache directory
+/// for a built-in dictionary
+fn cache_path(args: &Args) -> Option<PathBuf> {
+    match (&args.final_set, &args.dictionary) {
+        (Some(final_set), _) => Some(PathBuf::from(format!("{final_set}.ratings.json"))),
+        (None, Some(dictionary)) => {
+            Some(cache::cache_dir()?.join(format!("{dictionary}.ratings.json")))
+        }
+        (None, None) => None,
+    }
+}
+
+/// The ratings of the final words, computed once and then read from the cache
+pub fn ratings(args: &Args, final_words: &WordList) -> BTreeMap<String, Rating> {
+    let hash = cache::hash_words(final_words.iter());
+    let path = cache_path(args);
+    if let Some(ratings) = path.as_ref().and_then(|path| cache::load(path, hash)) {
+        return ratings;
+    }
+    let ratings = rate(final_words);
+    if let Some(path) = &path {
+        cache::store(path, hash, &ratings);
+    }
+    ratings
+}
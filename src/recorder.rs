@@ -1,6 +1,12 @@
+use crate::feedback::Feedback;
+use crate::game::MAX_ATTEMPTS;
+use crate::messages;
+use crate::state;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 struct GameStat {
     win: bool,
@@ -18,6 +24,8 @@ pub struct GameRecorder {
     tried_words: HashMap<String, u32>,
     win: u32,
     lose: u32,
+    /// Games left before they were over, not counted as won or lost
+    abandoned: u32,
 }
 
 impl GameRecorder {
@@ -27,6 +35,7 @@ impl GameRecorder {
             tried_words: HashMap::new(),
             win: 0,
             lose: 0,
+            abandoned: 0,
         }
     }
 
@@ -39,37 +48,28 @@ impl GameRecorder {
         }
     }
 
+    pub fn add_abandoned(&mut self) {
+        self.abandoned += 1;
+    }
+
     pub fn add_tried_word(&mut self, word: String) {
         *self.tried_words.entry(word).or_insert(0) += 1;
     }
 
-    fn print_top_5_words(&self) {
-        let mut sorted_words: Vec<(&str, u32)> = self
-            .tried_words
-            .iter()
-            .map(|(word, count)| (word.as_str(), *count))
-            .collect();
-        sorted_words.sort_by(|a, b| {
-            if b.1.cmp(&a.1) == Ordering::Equal {
-                a.0.cmp(b.0)
-            } else {
-                b.1.cmp(&a.1)
-            }
-        });
-        // for (word, count) in sorted_words.iter().take(5) {
-        //     print!("{} {} ", word.to_uppercase(), count);
-        // }
-        let mut iter = sorted_words.iter().take(5).peekable();
-        while let Some((word, count)) = iter.next() {
-            if iter.peek().is_some() {
-                print!("{} {} ", word.to_uppercase(), count);
-            } else {
-                print!("{} {}", word.to_uppercase(), count);
-            }
-        }
+    pub fn wins(&self) -> u32 {
+        self.win
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.lose
     }
 
-    pub fn print(&self) {
+    pub fn abandoned(&self) -> u32 {
+        self.abandoned
+    }
+
+    /// Average attempts of the won games, 0 if none was won
+    pub fn average_attempts(&self) -> f64 {
         let mut average_attempts: f64 = 0.0;
         let num_of_wins: u32 = self.games.iter().fold(0, |acc, game| acc + game.win as u32);
         if num_of_wins != 0 {
@@ -82,15 +82,119 @@ impl GameRecorder {
                 ) as f64
                     / num_of_wins as f64;
         }
-        println!("{} {} {:.2}", self.win, self.lose, average_attempts);
-        self.print_top_5_words();
-        println!();
+        average_attempts
+    }
+
+    /// Won games by attempts, from 1 to [`MAX_ATTEMPTS`], then the lost games
+    pub fn distribution(&self) -> [u32; MAX_ATTEMPTS as usize + 1] {
+        let mut distribution = [0; MAX_ATTEMPTS as usize + 1];
+        for game in &self.games {
+            let idx = if game.win {
+                (game.attempts as usize).clamp(1, MAX_ATTEMPTS as usize) - 1
+            } else {
+                MAX_ATTEMPTS as usize
+            };
+            distribution[idx] += 1;
+        }
+        distribution
+    }
+
+    /// The `count` most tried words and how often, the most tried first
+    pub fn top_words(&self, count: usize) -> Vec<(&str, u32)> {
+        let mut sorted_words: Vec<(&str, u32)> = self
+            .tried_words
+            .iter()
+            .map(|(word, count)| (word.as_str(), *count))
+            .collect();
+        sorted_words.sort_by(|a, b| {
+            if b.1.cmp(&a.1) == Ordering::Equal {
+                a.0.cmp(b.0)
+            } else {
+                b.1.cmp(&a.1)
+            }
+        });
+        sorted_words.truncate(count);
+        sorted_words
+    }
+
+    /// The 5 most tried words and their counts, e.g. `CRANE 2 AUDIO 1`
+    fn top_5_words(&self) -> String {
+        self.top_words(5)
+            .iter()
+            .map(|(word, count)| format!("{} {}", word.to_uppercase(), count))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The lines of the statistics, the counts of the games and the most tried words
+    pub fn summary(&self) -> [String; 2] {
+        let messages = messages::get();
+        [
+            messages.stats(self.win, self.lose, self.abandoned, self.average_attempts()),
+            messages.top_words(&self.top_5_words()),
+        ]
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A finished game. Games imported from elsewhere may only know the feedback of
+/// each guess, their answer and guesses being empty then.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SingleGameData {
+    #[serde(default)]
     pub answer: String,
+    #[serde(default)]
+    pub guesses: Vec<String>,
+
+    /// The `G/Y/R` codes of each guess, only stored for imported games
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feedback: Vec<String>,
+
+    /// The number of the daily puzzle, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<u32>,
+
+    /// Left before it was over, when the input ended
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub abandoned: bool,
+
+    /// Played in difficult mode
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub difficult: bool,
+
+    /// When the game ended, in milliseconds since the Unix epoch, only recorded
+    /// with `--timestamps`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub played_at: Option<u64>,
+}
+
+impl SingleGameData {
+    pub fn is_win(&self) -> bool {
+        if self.abandoned {
+            return false;
+        }
+        match self.feedback.last() {
+            Some(feedback) => feedback
+                .parse::<Feedback>()
+                .is_ok_and(|feedback| feedback.is_win()),
+            None => !self.guesses.is_empty() && self.guesses.last() == Some(&self.answer),
+        }
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.guesses.len().max(self.feedback.len())
+    }
+
+    /// Whether the answer and every guess are known
+    pub fn is_complete(&self) -> bool {
+        !self.answer.is_empty() && !self.guesses.is_empty()
+    }
+}
+
+/// A game interrupted before it was over, to be resumed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnfinishedGame {
+    pub answer: String,
+    pub difficult: bool,
     pub guesses: Vec<String>,
 }
 
@@ -101,6 +205,23 @@ pub struct GameData {
 
     #[serde(default)]
     pub games: Vec<SingleGameData>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unfinished: Option<UnfinishedGame>,
+    /// Whether this session started, resumed or dropped the unfinished game, which
+    /// is otherwise left as saved by other sessions
+    #[serde(skip)]
+    pub unfinished_changed: bool,
+
+    /// The games and rounds in the state file when it was last loaded or saved, the
+    /// games after them being new in this session
+    #[serde(skip)]
+    pub saved_games: usize,
+    #[serde(skip)]
+    pub saved_rounds: u32,
+    /// Whether this session already kept a backup of the state file
+    #[serde(skip)]
+    pub backed_up: bool,
 }
 
 impl GameData {
@@ -108,25 +229,66 @@ impl GameData {
         GameData {
             total_rounds: 0,
             games: Vec::new(),
+            unfinished: None,
+            unfinished_changed: false,
+            saved_games: 0,
+            saved_rounds: 0,
+            backed_up: false,
         }
     }
 
-    pub fn add_game(&mut self, guess_results: &crate::game::Guess, ans: &str) {
+    /// Add a game that just ended, with its mode, and its time with `--timestamps`
+    pub fn add_game(
+        &mut self,
+        args: &crate::args::Args,
+        guess_results: &crate::game::Guess,
+        ans: &str,
+        difficult: bool,
+        abandoned: bool,
+    ) {
+        let played_at = args.timestamps.then(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64)
+        });
         self.total_rounds += 1;
         self.games.push(SingleGameData {
+            abandoned,
+            difficult,
+            played_at,
             answer: ans.to_string().to_uppercase(),
             guesses: guess_results
                 .history
                 .iter()
                 .map(|guess_content| guess_content.content.clone().to_uppercase())
                 .collect(),
+            ..Default::default()
         });
     }
 
-    pub fn save(&self, args: &crate::args::Args) -> Result<(), Box<dyn std::error::Error>> {
-        let file_path = args.state.clone().unwrap();
+    pub fn set_unfinished(&mut self, game: Option<UnfinishedGame>) {
+        self.unfinished = game;
+        self.unfinished_changed = true;
+    }
+
+    pub fn read(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = std::fs::File::open(path).map_err(|err| format!("{path}: {err}"))?;
+        let mut data: Self = serde_json::from_reader(std::io::BufReader::new(file))
+            .map_err(|err| format!("Invalid state file {path}: {err}"))?;
+        data.saved_games = data.games.len();
+        data.saved_rounds = data.total_rounds;
+        Ok(data)
+    }
+
+    /// Write the data to a new file, at once
+    pub fn write(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(file_path, json)?;
+        state::write_atomic(Path::new(path), &json)?;
         Ok(())
     }
+
+    /// Save the new games to the state file, see [`state::save`]
+    pub fn save(&mut self, args: &crate::args::Args) -> Result<(), Box<dyn std::error::Error>> {
+        state::save(self, args.state.as_deref().unwrap(), args.backups)
+    }
 }
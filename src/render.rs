@@ -0,0 +1,333 @@
+use crate::args::Args;
+use crate::game::{CharStatus, Guess, GuessResult, Outcome};
+use crate::messages;
+use crate::recorder::GameRecorder;
+use crate::theme::Theme;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Front-ends showing the board, chosen with `--renderer`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RendererKind {
+    /// Text, like `tty` on a tty and like `plain` otherwise
+    #[default]
+    Auto,
+    /// Text defaulting to coloured letters and the whole history
+    Tty,
+    /// Text defaulting to `G/Y/R/X` codes of the last guess only
+    Plain,
+    /// One JSON object per line
+    Json,
+    /// Plain sentences spelling out every letter
+    ScreenReader,
+}
+
+/// Shows the board to the player. Scoring is done before a renderer is called,
+/// so a new front-end only needs to implement this trait.
+pub trait Renderer {
+    /// Whether a human reads the output, so friendly messages should be used
+    fn interactive(&self) -> bool;
+
+    /// Show the board after a new guess
+    fn board(&self, guesses: &Guess);
+
+    /// Show a race opponent's row, without the letters
+    fn opponent(&self, name: &str, status: &[CharStatus]);
+
+    /// Show a message, such as a rejected guess or a prompt
+    fn message(&self, text: &str) {
+        println!("{text}");
+    }
+
+    /// Show how a game against `answer` ended after `attempts` guesses
+    fn result(&self, outcome: Outcome, attempts: u32, answer: &str) {
+        let messages = messages::get();
+        self.message(&match outcome {
+            Outcome::Won => messages.correct(attempts),
+            Outcome::Lost => messages.failed(answer),
+            Outcome::Abandoned => messages.abandoned(answer),
+        });
+    }
+
+    /// Show the statistics of the games so far
+    fn stats(&self, game_recorder: &GameRecorder) {
+        for line in game_recorder.summary() {
+            self.message(&line);
+        }
+    }
+}
+
+/// Whether the text renderer uses colours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// Colours on a tty only
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Which rows the text renderer prints after each guess
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// The whole history on a tty, the last row otherwise
+    #[default]
+    Auto,
+    History,
+    Last,
+}
+
+/// What the text renderer prints for each letter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// Letters on a tty, codes otherwise
+    #[default]
+    Auto,
+    /// The letters themselves, showing the result through colours only
+    Letters,
+    /// `G/Y/R/X` codes
+    Codes,
+}
+
+pub fn new_renderer(args: &Args, is_tty: bool) -> Box<dyn Renderer> {
+    let kind = args.renderer.unwrap_or_default();
+    // the tty and plain renderers are presets of the text renderer
+    let tty_like = match kind {
+        RendererKind::Json => return Box::new(JsonRenderer),
+        RendererKind::ScreenReader => return Box::new(ScreenReaderRenderer),
+        RendererKind::Auto => is_tty,
+        RendererKind::Tty => true,
+        RendererKind::Plain => false,
+    };
+    let color = match args.color.unwrap_or_default() {
+        ColorChoice::Auto => tty_like,
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+    let layout = match args.layout.unwrap_or_default() {
+        Layout::Auto if tty_like => Layout::History,
+        Layout::Auto => Layout::Last,
+        layout => layout,
+    };
+    let format = match args.format.unwrap_or_default() {
+        Format::Auto if tty_like => Format::Letters,
+        Format::Auto => Format::Codes,
+        format => format,
+    };
+    Box::new(TextRenderer {
+        theme: color.then(|| Theme::new(args.theme.unwrap_or_default())),
+        layout,
+        format,
+        interactive: is_tty,
+    })
+}
+
+fn codes(status: &[CharStatus]) -> String {
+    status.iter().map(|status| status.code()).collect()
+}
+
+fn keyboard_codes(guess_result: &GuessResult) -> String {
+    guess_result
+        .keyboard()
+        .values()
+        .map(|status| status.code())
+        .collect()
+}
+
+/// Rows of letters or codes, optionally coloured
+pub struct TextRenderer {
+    theme: Option<Theme>,
+    layout: Layout,
+    format: Format,
+    /// Only a tty gets friendly messages, whatever the presentation is
+    interactive: bool,
+}
+
+impl TextRenderer {
+    fn paint(&self, text: &str, status: CharStatus) -> String {
+        match &self.theme {
+            Some(theme) => theme.paint(text, status),
+            None => text.to_string(),
+        }
+    }
+
+    fn cell(&self, letter: char, status: CharStatus) -> String {
+        match self.format {
+            Format::Codes => self.paint(&status.code().to_string(), status),
+            _ => self.paint(&letter.to_uppercase().to_string(), status),
+        }
+    }
+
+    fn row(&self, guess_result: &GuessResult) -> String {
+        let mut row = String::new();
+        for (status, guess_char) in guess_result
+            .status()
+            .iter()
+            .zip(guess_result.content.chars())
+        {
+            row += &self.cell(guess_char, *status);
+        }
+        row.push(' ');
+        for (key, status) in guess_result.keyboard() {
+            row += &self.cell(*key, *status);
+        }
+        row
+    }
+}
+
+impl Renderer for TextRenderer {
+    fn interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn board(&self, guesses: &Guess) {
+        match self.layout {
+            Layout::Last => println!("{}", self.row(guesses.history.last().unwrap())),
+            _ => {
+                for guess_result in &guesses.history {
+                    println!("{}", self.row(guess_result));
+                }
+            }
+        }
+    }
+
+    fn opponent(&self, name: &str, status: &[CharStatus]) {
+        // opponents' letters stay hidden, so coloured blocks stand in for them
+        let cells: String = status
+            .iter()
+            .map(|status| match (&self.theme, self.format) {
+                (Some(theme), Format::Letters) => theme.paint("■", *status),
+                _ => self.paint(&status.code().to_string(), *status),
+            })
+            .collect();
+        println!("{name} {cells}");
+    }
+}
+
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn interactive(&self) -> bool {
+        false
+    }
+
+    fn board(&self, guesses: &Guess) {
+        let last = guesses.history.last().unwrap();
+        let row = json!({
+            "attempt": guesses.history.len(),
+            "guess": last.content.to_uppercase(),
+            "result": codes(last.status()),
+            "keyboard": keyboard_codes(last),
+        });
+        println!("{row}");
+    }
+
+    fn opponent(&self, name: &str, status: &[CharStatus]) {
+        let row = json!({
+            "player": name,
+            "result": codes(status),
+        });
+        println!("{row}");
+    }
+
+    fn message(&self, text: &str) {
+        println!("{}", json!({ "message": text }));
+    }
+
+    fn result(&self, outcome: Outcome, attempts: u32, answer: &str) {
+        let outcome = match outcome {
+            Outcome::Won => "won",
+            Outcome::Lost => "lost",
+            Outcome::Abandoned => "abandoned",
+        };
+        let row = json!({
+            "outcome": outcome,
+            "attempts": attempts,
+            "answer": answer.to_uppercase(),
+        });
+        println!("{row}");
+    }
+
+    fn stats(&self, game_recorder: &GameRecorder) {
+        let top_words: Vec<_> = game_recorder
+            .top_words(5)
+            .into_iter()
+            .map(|(word, count)| json!([word.to_uppercase(), count]))
+            .collect();
+        let row = json!({
+            "wins": game_recorder.wins(),
+            "losses": game_recorder.losses(),
+            "abandoned": game_recorder.abandoned(),
+            "average_attempts": game_recorder.average_attempts(),
+            "top_words": top_words,
+        });
+        println!("{row}");
+    }
+}
+
+pub struct ScreenReaderRenderer;
+
+impl ScreenReaderRenderer {
+    /// Keyboard letters with `status`, e.g. `A, E`, or `none`
+    fn keys_with(guess_result: &GuessResult, status: CharStatus) -> String {
+        let keys: Vec<String> = guess_result
+            .keyboard()
+            .iter()
+            .filter(|(_, key_status)| **key_status == status)
+            .map(|(key, _)| key.to_uppercase().to_string())
+            .collect();
+        messages::get().reader_list(&keys)
+    }
+}
+
+impl Renderer for ScreenReaderRenderer {
+    fn interactive(&self) -> bool {
+        true
+    }
+
+    fn board(&self, guesses: &Guess) {
+        let messages = messages::get();
+        let last = guesses.history.last().unwrap();
+        let letters: Vec<String> = last
+            .content
+            .chars()
+            .zip(last.status())
+            .map(|(guess_char, status)| {
+                format!(
+                    "{} {}",
+                    guess_char.to_uppercase(),
+                    messages.reader_status(*status)
+                )
+            })
+            .collect();
+        println!(
+            "{}",
+            messages.reader_guess(guesses.history.len(), &messages.reader_list(&letters))
+        );
+        println!(
+            "{}",
+            messages.reader_keyboard(
+                &Self::keys_with(last, CharStatus::Correct),
+                &Self::keys_with(last, CharStatus::WrongPosition),
+                &Self::keys_with(last, CharStatus::TooMany),
+            )
+        );
+    }
+
+    fn opponent(&self, name: &str, status: &[CharStatus]) {
+        let count = |wanted: CharStatus| status.iter().filter(|s| **s == wanted).count();
+        println!(
+            "{}",
+            messages::get().reader_opponent(
+                name,
+                count(CharStatus::Correct),
+                count(CharStatus::WrongPosition)
+            )
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::args::{Args, SolveArgs};
+use crate::bot::ExternalSolver;
+use crate::feedback::Feedback;
+use crate::game;
+use crate::messages;
+use crate::tournament::{self, Mode};
+use crate::words::WordList;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::io;
+
+/// Prefix of a strategy run by an external program, e.g. `bot:python3 solver.py`
+const BOT_PREFIX: &str = "bot:";
+
+/// Names of the built-in strategies
+pub const BUILTIN_STRATEGIES: [&str; 3] = ["first", "frequency", "random"];
+
+/// Something that plays wordle on its own. A game goes `start`, then `guess` until the
+/// game is over, each valid guess being followed by `feedback` unless the game is over,
+/// and each rejected guess by `rejected`, and finally `finish`.
+pub trait Solver {
+    fn name(&self) -> &str;
+
+    fn start(&mut self, word_length: usize, difficult: bool) -> io::Result<()>;
+
+    fn guess(&mut self) -> io::Result<String>;
+
+    fn feedback(&mut self, guess: &str, feedback: Feedback) -> io::Result<()>;
+
+    fn rejected(&mut self, guess: &str) -> io::Result<()>;
+
+    fn finish(&mut self, win: bool, attempts: usize, ans: &str) -> io::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Strategy {
+    /// The first candidate in list order
+    First,
+    /// The candidate whose letters are the most common among the candidates
+    Frequency,
+    /// A random candidate
+    Random,
+}
+
+/// Guess only words that can still be the answer, so difficult mode is always respected
+pub struct CandidateSolver {
+    name: String,
+    strategy: Strategy,
+    final_words: WordList,
+    /// Indices of the final words that can still be the answer
+    candidates: Vec<usize>,
+    rng: StdRng,
+}
+
+impl CandidateSolver {
+    fn new(name: &str, strategy: Strategy, final_words: &WordList, seed: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            strategy,
+            final_words: final_words.clone(),
+            candidates: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn most_frequent(&self) -> Option<&usize> {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for candidate in &self.candidates {
+            for letter in distinct_letters(self.final_words.get(*candidate)) {
+                *counts.entry(letter).or_insert(0) += 1;
+            }
+        }
+        // max_by_key keeps the last maximum, so search backwards to prefer the first
+        self.candidates.iter().rev().max_by_key(|candidate| {
+            distinct_letters(self.final_words.get(**candidate))
+                .iter()
+                .map(|letter| counts[letter])
+                .sum::<usize>()
+        })
+    }
+}
+
+fn distinct_letters(word: &str) -> Vec<char> {
+    let mut letters: Vec<char> = word.chars().collect();
+    letters.sort_unstable();
+    letters.dedup();
+    letters
+}
+
+impl Solver for CandidateSolver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self, _word_length: usize, _difficult: bool) -> io::Result<()> {
+        self.candidates = (0..self.final_words.len()).collect();
+        Ok(())
+    }
+
+    fn guess(&mut self) -> io::Result<String> {
+        let guess = match self.strategy {
+            Strategy::First => self.candidates.first(),
+            Strategy::Frequency => self.most_frequent(),
+            Strategy::Random => self.candidates.choose(&mut self.rng),
+        };
+        guess
+            .map(|candidate| self.final_words.get(*candidate).to_string())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} has no candidate left", self.name),
+                )
+            })
+    }
+
+    fn feedback(&mut self, guess: &str, feedback: Feedback) -> io::Result<()> {
+        let final_words = &self.final_words;
+        self.candidates
+            .retain(|candidate| game::score(final_words.get(*candidate), guess) == Ok(feedback));
+        Ok(())
+    }
+
+    fn rejected(&mut self, guess: &str) -> io::Result<()> {
+        let final_words = &self.final_words;
+        self.candidates
+            .retain(|candidate| final_words.get(*candidate) != guess);
+        Ok(())
+    }
+
+    fn finish(&mut self, _win: bool, _attempts: usize, _ans: &str) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Create the solver of a strategy: one of [`BUILTIN_STRATEGIES`], or `bot:<command>`
+pub fn new_solver(
+    spec: &str,
+    final_words: &WordList,
+    seed: u64,
+) -> Result<Box<dyn Solver>, Box<dyn std::error::Error>> {
+    if let Some(command) = spec.strip_prefix(BOT_PREFIX) {
+        return Ok(Box::new(ExternalSolver::new(spec, command)?));
+    }
+    let strategy = match spec {
+        "first" => Strategy::First,
+        "frequency" => Strategy::Frequency,
+        "random" => Strategy::Random,
+        _ => {
+            return Err(format!(
+                "Unknown strategy {spec}, expected one of {} or {BOT_PREFIX}<command>",
+                BUILTIN_STRATEGIES.join(", ")
+            )
+            .into());
+        }
+    };
+    Ok(Box::new(CandidateSolver::new(
+        spec,
+        strategy,
+        final_words,
+        seed,
+    )))
+}
+
+/// Let a strategy solve one answer, printing each guess with its feedback
+pub fn run(args: &Args, solve: &SolveArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut final_words = WordList::default();
+    let mut acceptable = WordList::default();
+    game::init_game(args, &mut final_words, &mut acceptable)?;
+    let ans = solve.answer.to_lowercase();
+    if !final_words.contains(&ans) {
+        return Err(format!("Unknown answer {}, it must be a final word", solve.answer).into());
+    }
+
+    let mut solver = new_solver(&solve.strategy, &final_words, args.seed.unwrap_or_default())?;
+    let mode = if args.difficult {
+        Mode::Difficult
+    } else {
+        Mode::Normal
+    };
+    let record = tournament::play(solver.as_mut(), mode, &ans, &acceptable)?;
+    for guess in &record.guesses {
+        println!("{guess} {}", game::score(&ans, &guess.to_lowercase())?);
+    }
+    if record.win {
+        println!("{}", messages::get().solved(record.attempts));
+    } else {
+        println!("{}", messages::get().not_solved(&record.answer));
+    }
+    Ok(())
+}
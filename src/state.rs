@@ -0,0 +1,242 @@
+use crate::args::MergeArgs;
+use crate::messages;
+use crate::recorder::{GameData, SingleGameData};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Backups kept of a state file, `state.json.1` being the newest
+const BACKUPS: usize = 3;
+
+/// `path` with `suffix` appended to its file name, e.g. `state.json.1`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Replace `path` by a new file, written to a temporary file first, so a crash
+/// leaves either the old or the new content
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let temporary = with_suffix(path, &format!(".tmp{}", std::process::id()));
+    let written = File::create(&temporary).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    let result = written.and_then(|()| fs::rename(&temporary, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Copy the file to `path.1`, after moving the older backups to `path.2` and so on
+fn rotate_backups(path: &Path) -> io::Result<()> {
+    for number in (1..BACKUPS).rev() {
+        let backup = with_suffix(path, &format!(".{number}"));
+        if backup.exists() {
+            fs::rename(&backup, with_suffix(path, &format!(".{}", number + 1)))?;
+        }
+    }
+    fs::copy(path, with_suffix(path, ".1"))?;
+    Ok(())
+}
+
+/// Whether `file` is still the file at `path`, and not one it replaced
+#[cfg(unix)]
+fn is_current(file: &File, path: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let locked = file.metadata()?;
+    match fs::metadata(path) {
+        Ok(current) => Ok(locked.dev() == current.dev() && locked.ino() == current.ino()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Without inodes to compare, other systems rely on the lock alone
+#[cfg(not(unix))]
+fn is_current(_file: &File, _path: &Path) -> io::Result<bool> {
+    Ok(true)
+}
+
+/// Open and lock the state file, creating it empty if it is missing. A session
+/// saving meanwhile may replace the file while this one waits for the lock, and
+/// the replaced file keeps no one out, so the new one is locked instead.
+fn lock(path: &Path) -> io::Result<File> {
+    loop {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(path)?;
+        file.lock()?;
+        if is_current(&file, path)? {
+            return Ok(file);
+        }
+    }
+}
+
+/// Held while saving, so an interrupted session never exits in the middle of a save
+static SAVING: Mutex<()> = Mutex::new(());
+
+/// Save the games to the state file, holding an advisory lock on it so sessions
+/// sharing the file save one at a time. The file is read again first: the games other
+/// sessions saved since it was loaded are kept, followed by the games new in this
+/// session. A file that is not valid JSON is left untouched and an error returned.
+/// With `backups`, the file is backed up the first time this session adds games.
+pub fn save(
+    data: &mut GameData,
+    path: &str,
+    backups: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let _saving = SAVING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = Path::new(path);
+    let mut file = lock(path)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    // a file just created by the lock is empty, like a missing one
+    let on_disk = !content.trim().is_empty();
+    if on_disk {
+        let on_disk: GameData = serde_json::from_str(&content)
+            .map_err(|err| format!("Invalid state file {}: {err}", path.display()))?;
+        let new_games = data.games.split_off(data.saved_games.min(data.games.len()));
+        let new_rounds = data.total_rounds.saturating_sub(data.saved_rounds);
+        data.games = on_disk.games;
+        data.games.extend(new_games);
+        data.total_rounds = on_disk.total_rounds + new_rounds;
+        if !data.unfinished_changed {
+            data.unfinished = on_disk.unfinished;
+        }
+    }
+    if backups && on_disk && !data.backed_up && data.games.len() > data.saved_games {
+        rotate_backups(path)?;
+        data.backed_up = true;
+    }
+    write_atomic(path, &serde_json::to_string_pretty(data)?)?;
+    data.saved_games = data.games.len();
+    data.saved_rounds = data.total_rounds;
+    data.unfinished_changed = false;
+    Ok(())
+}
+
+/// On Ctrl-C, wait for a save in progress to finish and exit. The progress of a game
+/// is saved after every guess, so nothing is lost.
+pub fn exit_on_interrupt() -> Result<(), Box<dyn std::error::Error>> {
+    ctrlc::set_handler(|| {
+        let _saving = SAVING
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        eprintln!("\n{}", messages::get().interrupted());
+        std::process::exit(130);
+    })?;
+    Ok(())
+}
+
+/// The guesses of a game, or their feedback if the guesses are unknown
+fn rows(game: &SingleGameData) -> String {
+    if game.guesses.is_empty() {
+        game.feedback.join(" ")
+    } else {
+        game.guesses.join(" ")
+    }
+}
+
+/// A short description of a game for messages, e.g. `GRAND (CRANE GRAND)`
+fn describe(game: &SingleGameData) -> String {
+    let name = if game.answer.is_empty() {
+        messages::get().game_of_day(game.day)
+    } else {
+        game.answer.to_uppercase()
+    };
+    format!("{name} ({})", rows(game))
+}
+
+/// Union the games of several state files, in the order they are first found.
+///
+/// A game with a timestamp is kept once, however many files have it. Identical games
+/// without one are told apart by how many times each file has them: such a game is
+/// kept as many times as the file having it most, as files copied from one another
+/// have the same games. Returns the merged data and a message for every conflict:
+/// identical games without a timestamp whose counts differ between files, which may
+/// also have been played again, and different games of the same day.
+pub fn merge(files: &[(String, GameData)]) -> (GameData, Vec<String>) {
+    let mut merged = GameData::new();
+    // how many times each game is in each file, and in the merged data
+    let mut counts: Vec<HashMap<&SingleGameData, usize>> = Vec::new();
+    let mut kept: HashMap<&SingleGameData, usize> = HashMap::new();
+    for (_, data) in files {
+        let mut file_counts = HashMap::new();
+        for game in &data.games {
+            let count = file_counts.entry(game).or_insert(0);
+            *count += 1;
+            let kept = kept.entry(game).or_insert(0);
+            let wanted = if game.played_at.is_some() { 1 } else { *count };
+            if wanted > *kept {
+                *kept += 1;
+                merged.games.push(game.clone());
+            }
+        }
+        counts.push(file_counts);
+    }
+    merged.total_rounds = merged.games.len() as u32;
+
+    let mut conflicts = Vec::new();
+    let mut reported = Vec::new();
+    for game in &merged.games {
+        if game.played_at.is_some() || reported.contains(&game) {
+            continue;
+        }
+        reported.push(game);
+        let found: Vec<(&str, usize)> = files
+            .iter()
+            .zip(&counts)
+            .filter_map(|((name, _), counts)| Some((name.as_str(), *counts.get(game)?)))
+            .collect();
+        if found.iter().any(|(_, count)| *count != found[0].1) {
+            conflicts.push(messages::get().count_conflict(&describe(game), &found, kept[game]));
+        }
+    }
+
+    // different games of the same daily puzzle, which can only be played once
+    let mut days: HashMap<u32, Vec<&SingleGameData>> = HashMap::new();
+    for game in &merged.games {
+        if let Some(day) = game.day {
+            days.entry(day).or_default().push(game);
+        }
+    }
+    let mut days: Vec<_> = days
+        .into_iter()
+        .filter(|(_, games)| games.len() > 1)
+        .collect();
+    days.sort_by_key(|(day, _)| *day);
+    for (day, games) in days {
+        let games: Vec<String> = games.into_iter().map(rows).collect();
+        conflicts.push(messages::get().day_conflict(day, &games.join(", ")));
+    }
+    (merged, conflicts)
+}
+
+/// Merge the given state files into a new one and report the conflicts
+pub fn run_merge(merge_args: &MergeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for path in &merge_args.files {
+        files.push((path.clone(), GameData::read(path)?));
+    }
+    let (merged, conflicts) = merge(&files);
+    merged.write(&merge_args.output)?;
+    println!(
+        "{}",
+        messages::get().merged(merged.games.len(), files.len(), &merge_args.output)
+    );
+    for conflict in conflicts {
+        println!("{conflict}");
+    }
+    Ok(())
+}
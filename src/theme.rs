@@ -0,0 +1,237 @@
+use crate::game::CharStatus;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Named colour themes for the board and keyboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Classic,
+    /// Orange and blue instead of green and yellow
+    ColorBlind,
+    /// Bold letters on bright backgrounds
+    HighContrast,
+    /// No colours, only bold, underline and faint text
+    Monochrome,
+}
+
+/// How many colours the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorDepth {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Honour `NO_COLOR`, then guess from `COLORTERM` and `TERM`
+    fn detect() -> Self {
+        Self::from_vars(
+            &std::env::var("NO_COLOR").unwrap_or_default(),
+            &std::env::var("COLORTERM").unwrap_or_default(),
+            &std::env::var("TERM").unwrap_or_default(),
+        )
+    }
+
+    fn from_vars(no_color: &str, colorterm: &str, term: &str) -> Self {
+        if !no_color.is_empty() {
+            ColorDepth::None
+        } else if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term == "dumb" {
+            ColorDepth::None
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+}
+
+/// A colour in each depth: the 16 colour SGR code, the 256 colour index and RGB
+type Color = (u8, u8, (u8, u8, u8));
+
+const GREEN: Color = (32, 34, (106, 170, 100));
+const YELLOW: Color = (33, 178, (201, 180, 88));
+const RED: Color = (31, 160, (200, 60, 60));
+const ORANGE: Color = (33, 208, (245, 121, 58));
+const BLUE: Color = (34, 33, (133, 192, 249));
+const GREY: Color = (90, 244, (120, 124, 126));
+const BRIGHT_GREEN: Color = (92, 46, (0, 230, 64));
+const BRIGHT_YELLOW: Color = (93, 226, (255, 230, 0));
+const BRIGHT_RED: Color = (91, 196, (255, 40, 40));
+
+pub struct Theme {
+    name: ThemeName,
+    depth: ColorDepth,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        Self {
+            name,
+            depth: ColorDepth::detect(),
+        }
+    }
+
+    /// SGR parameters selecting `color` as the foreground
+    fn foreground(&self, (ansi16, ansi256, (r, g, b)): Color) -> String {
+        match self.depth {
+            ColorDepth::None => String::new(),
+            ColorDepth::Ansi16 => ansi16.to_string(),
+            ColorDepth::Ansi256 => format!("38;5;{ansi256}"),
+            ColorDepth::TrueColor => format!("38;2;{r};{g};{b}"),
+        }
+    }
+
+    /// SGR parameters selecting `color` as the background, with bold black text
+    fn background(&self, (ansi16, ansi256, (r, g, b)): Color) -> String {
+        match self.depth {
+            ColorDepth::None => String::new(),
+            ColorDepth::Ansi16 => format!("1;30;{}", ansi16 + 10),
+            ColorDepth::Ansi256 => format!("1;38;5;16;48;5;{ansi256}"),
+            ColorDepth::TrueColor => format!("1;38;2;0;0;0;48;2;{r};{g};{b}"),
+        }
+    }
+
+    fn monochrome(status: CharStatus) -> String {
+        match status {
+            CharStatus::Correct => "1;4",
+            CharStatus::WrongPosition => "4",
+            CharStatus::TooMany => "2",
+            CharStatus::Unknown => "",
+        }
+        .to_string()
+    }
+
+    fn style(&self, status: CharStatus) -> String {
+        if self.depth == ColorDepth::None {
+            return Self::monochrome(status);
+        }
+        match (self.name, status) {
+            (_, CharStatus::Unknown) => String::new(),
+            (ThemeName::Monochrome, _) => Self::monochrome(status),
+            (ThemeName::Classic, CharStatus::Correct) => self.foreground(GREEN),
+            (ThemeName::Classic, CharStatus::WrongPosition) => self.foreground(YELLOW),
+            (ThemeName::Classic, CharStatus::TooMany) => self.foreground(RED),
+            (ThemeName::ColorBlind, CharStatus::Correct) => self.foreground(ORANGE),
+            (ThemeName::ColorBlind, CharStatus::WrongPosition) => self.foreground(BLUE),
+            (ThemeName::ColorBlind, CharStatus::TooMany) => self.foreground(GREY),
+            (ThemeName::HighContrast, CharStatus::Correct) => self.background(BRIGHT_GREEN),
+            (ThemeName::HighContrast, CharStatus::WrongPosition) => self.background(BRIGHT_YELLOW),
+            (ThemeName::HighContrast, CharStatus::TooMany) => self.background(BRIGHT_RED),
+        }
+    }
+
+    /// Wrap `text` in the escape codes of `status`
+    pub fn paint(&self, text: &str, status: CharStatus) -> String {
+        let style = self.style(status);
+        if style.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paint(name: ThemeName, depth: ColorDepth, status: CharStatus) -> String {
+        Theme { name, depth }.paint("A", status)
+    }
+
+    #[test]
+    fn test_detect_depth() {
+        let detect = ColorDepth::from_vars;
+        assert_eq!(detect("", "", "xterm"), ColorDepth::Ansi16);
+        assert_eq!(detect("", "", "xterm-256color"), ColorDepth::Ansi256);
+        assert_eq!(
+            detect("", "truecolor", "xterm-256color"),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect("", "24bit", ""), ColorDepth::TrueColor);
+        assert_eq!(detect("", "", "dumb"), ColorDepth::None);
+        assert_eq!(detect("1", "truecolor", "xterm-256color"), ColorDepth::None);
+    }
+
+    #[test]
+    fn test_paint_each_depth() {
+        let classic = |depth| paint(ThemeName::Classic, depth, CharStatus::Correct);
+        assert_eq!(classic(ColorDepth::Ansi16), "\x1b[32mA\x1b[0m");
+        assert_eq!(classic(ColorDepth::Ansi256), "\x1b[38;5;34mA\x1b[0m");
+        assert_eq!(
+            classic(ColorDepth::TrueColor),
+            "\x1b[38;2;106;170;100mA\x1b[0m"
+        );
+        let contrast = |depth| paint(ThemeName::HighContrast, depth, CharStatus::TooMany);
+        assert_eq!(contrast(ColorDepth::Ansi16), "\x1b[1;30;101mA\x1b[0m");
+        assert_eq!(
+            contrast(ColorDepth::Ansi256),
+            "\x1b[1;38;5;16;48;5;196mA\x1b[0m"
+        );
+        assert_eq!(
+            contrast(ColorDepth::TrueColor),
+            "\x1b[1;38;2;0;0;0;48;2;255;40;40mA\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_paint_each_theme() {
+        let statuses = [
+            CharStatus::Correct,
+            CharStatus::WrongPosition,
+            CharStatus::TooMany,
+            CharStatus::Unknown,
+        ];
+        let codes = |name| statuses.map(|status| paint(name, ColorDepth::Ansi16, status));
+        assert_eq!(
+            codes(ThemeName::Classic),
+            [
+                "\x1b[32mA\x1b[0m",
+                "\x1b[33mA\x1b[0m",
+                "\x1b[31mA\x1b[0m",
+                "A"
+            ]
+        );
+        assert_eq!(
+            codes(ThemeName::ColorBlind),
+            [
+                "\x1b[33mA\x1b[0m",
+                "\x1b[34mA\x1b[0m",
+                "\x1b[90mA\x1b[0m",
+                "A"
+            ]
+        );
+        assert_eq!(
+            codes(ThemeName::HighContrast),
+            [
+                "\x1b[1;30;102mA\x1b[0m",
+                "\x1b[1;30;103mA\x1b[0m",
+                "\x1b[1;30;101mA\x1b[0m",
+                "A"
+            ]
+        );
+        let monochrome = [
+            "\x1b[1;4mA\x1b[0m",
+            "\x1b[4mA\x1b[0m",
+            "\x1b[2mA\x1b[0m",
+            "A",
+        ];
+        assert_eq!(codes(ThemeName::Monochrome), monochrome);
+        // without colours, every theme falls back to monochrome
+        for name in [
+            ThemeName::Classic,
+            ThemeName::ColorBlind,
+            ThemeName::HighContrast,
+        ] {
+            assert_eq!(
+                statuses.map(|status| paint(name, ColorDepth::None, status)),
+                monochrome
+            );
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use crate::args::{Args, TournamentArgs};
+use crate::game::{self, Guess, MAX_ATTEMPTS};
+use crate::messages;
+use crate::solver::{self, BUILTIN_STRATEGIES, Solver};
+use crate::words::WordList;
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+/// Rejected guesses in a row after which a strategy loses the game
+const MAX_REJECTED: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Normal,
+    Difficult,
+}
+
+/// One game played by one strategy
+#[derive(Serialize)]
+pub struct GameRecord {
+    pub mode: Mode,
+    pub strategy: String,
+    pub answer: String,
+    pub win: bool,
+    pub attempts: usize,
+    pub guesses: Vec<String>,
+}
+
+impl Mode {
+    fn name(self) -> &'static str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::Difficult => "difficult",
+        }
+    }
+}
+
+impl GameRecord {
+    /// Attempts used, counting a failure as one more than allowed
+    fn score(&self) -> usize {
+        if self.win {
+            self.attempts
+        } else {
+            MAX_ATTEMPTS as usize + 1
+        }
+    }
+}
+
+/// A row of the leaderboard
+#[derive(Serialize)]
+struct Standing {
+    mode: Mode,
+    strategy: String,
+    games: usize,
+    failures: usize,
+    /// Average attempts of the won games
+    mean_guesses: f64,
+}
+
+/// A word two strategies needed different attempts for, in the head-to-head
+#[derive(Serialize)]
+struct Split {
+    mode: Mode,
+    answer: String,
+    strategies: [String; 2],
+    /// Attempts of each strategy, counting a failure as one more than allowed
+    scores: [usize; 2],
+}
+
+#[derive(Serialize)]
+struct Export<'a> {
+    games: &'a [GameRecord],
+    leaderboard: &'a [Standing],
+    head_to_head: &'a [Split],
+}
+
+/// Let `solver` play one game against `ans`
+pub fn play(
+    solver: &mut dyn Solver,
+    mode: Mode,
+    ans: &str,
+    acceptable: &WordList,
+) -> io::Result<GameRecord> {
+    let difficult = mode == Mode::Difficult;
+    let mut guesses = Guess::new();
+    let mut game_win = false;
+    let mut rejected = 0;
+    solver.start(ans.len(), difficult)?;
+    while guesses.history.len() < MAX_ATTEMPTS as usize {
+        let guess = solver.guess()?;
+        let submitted = if acceptable.contains(&guess) {
+            guesses.submit(difficult, &guess, ans)
+        } else {
+            None
+        };
+        match submitted {
+            None => {
+                rejected += 1;
+                if rejected >= MAX_REJECTED {
+                    break;
+                }
+                solver.rejected(&guess)?;
+            }
+            Some(win) => {
+                rejected = 0;
+                game_win = win;
+                if win || guesses.history.len() == MAX_ATTEMPTS as usize {
+                    break;
+                }
+                solver.feedback(&guess, guesses.history.last().unwrap().feedback())?;
+            }
+        }
+    }
+    solver.finish(game_win, guesses.history.len(), ans)?;
+    Ok(GameRecord {
+        mode,
+        strategy: solver.name().to_string(),
+        answer: ans.to_uppercase(),
+        win: game_win,
+        attempts: guesses.history.len(),
+        guesses: guesses
+            .history
+            .iter()
+            .map(|guess_result| guess_result.content.to_uppercase())
+            .collect(),
+    })
+}
+
+/// The games of `strategy` in `mode`, following the answer sequence
+fn games_of<'a>(mode: Mode, strategy: &str, records: &'a [GameRecord]) -> Vec<&'a GameRecord> {
+    records
+        .iter()
+        .filter(|record| record.mode == mode && record.strategy == strategy)
+        .collect()
+}
+
+fn standings(mode: Mode, strategies: &[String], records: &[GameRecord]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| {
+            let games = games_of(mode, strategy, records);
+            let won: Vec<usize> = games
+                .iter()
+                .filter(|record| record.win)
+                .map(|record| record.attempts)
+                .collect();
+            Standing {
+                mode,
+                strategy: strategy.clone(),
+                games: games.len(),
+                failures: games.len() - won.len(),
+                mean_guesses: if won.is_empty() {
+                    0.0
+                } else {
+                    won.iter().sum::<usize>() as f64 / won.len() as f64
+                },
+            }
+        })
+        .collect();
+    standings.sort_by(|a, b| {
+        a.failures
+            .cmp(&b.failures)
+            .then(a.mean_guesses.total_cmp(&b.mean_guesses))
+    });
+    standings
+}
+
+/// Words where `a` needed fewer, as many and more attempts than `b`
+fn head_to_head(a: &[&GameRecord], b: &[&GameRecord]) -> (usize, usize, usize) {
+    let mut result = (0, 0, 0);
+    for (a, b) in a.iter().zip(b) {
+        match a.score().cmp(&b.score()) {
+            std::cmp::Ordering::Less => result.0 += 1,
+            std::cmp::Ordering::Equal => result.1 += 1,
+            std::cmp::Ordering::Greater => result.2 += 1,
+        }
+    }
+    result
+}
+
+/// The words each pair of strategies needed different attempts for
+fn splits(mode: Mode, strategies: &[String], records: &[GameRecord]) -> Vec<Split> {
+    let mut splits = Vec::new();
+    for (index, a) in strategies.iter().enumerate() {
+        for b in &strategies[index + 1..] {
+            let games = games_of(mode, a, records)
+                .into_iter()
+                .zip(games_of(mode, b, records));
+            splits.extend(
+                games
+                    .filter(|(a, b)| a.score() != b.score())
+                    .map(|(a, b)| Split {
+                        mode,
+                        answer: a.answer.clone(),
+                        strategies: [a.strategy.clone(), b.strategy.clone()],
+                        scores: [a.score(), b.score()],
+                    }),
+            );
+        }
+    }
+    splits
+}
+
+fn print_mode(mode: Mode, strategies: &[String], records: &[GameRecord], standings: &[Standing]) {
+    let games = standings.first().map_or(0, |standing| standing.games);
+    let messages = messages::get();
+    println!(
+        "{}",
+        messages.tournament_mode(mode == Mode::Difficult, games)
+    );
+    println!("{}", messages.tournament_columns());
+    for (rank, standing) in standings.iter().enumerate() {
+        println!(
+            "{:<4} {:>6.2} {:>8} {}",
+            rank + 1,
+            standing.mean_guesses,
+            standing.failures,
+            standing.strategy
+        );
+    }
+
+    // strategies are numbered, as bot commands are too long for a column
+    println!("{}", messages.head_to_head());
+    print!("{:<3}", "");
+    for column in 1..=strategies.len() {
+        print!(" {column:<11}");
+    }
+    println!();
+    for (index, row) in strategies.iter().enumerate() {
+        print!("{:<3}", index + 1);
+        for column in strategies {
+            let cell = if row == column {
+                "-".to_string()
+            } else {
+                // both lists follow the answer sequence, so games on the same word line up
+                let (wins, ties, losses) = head_to_head(
+                    &games_of(mode, row, records),
+                    &games_of(mode, column, records),
+                );
+                format!("{wins}/{ties}/{losses}")
+            };
+            print!(" {cell:<11}");
+        }
+        println!(" {row}");
+    }
+    println!();
+}
+
+/// Quote a CSV field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Write the games to a `.csv` file, or the games, the leaderboard and the words each
+/// pair of strategies split on to a JSON file
+fn export(
+    path: &str,
+    records: &[GameRecord],
+    standings: &[Standing],
+    splits: &[Split],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => {
+            let mut content = String::from("mode,strategy,answer,win,attempts,guesses\n");
+            for record in records {
+                content += &format!(
+                    "{},{},{},{},{},{}\n",
+                    record.mode.name(),
+                    csv_field(&record.strategy),
+                    record.answer,
+                    record.win,
+                    record.attempts,
+                    record.guesses.join(" ")
+                );
+            }
+            content
+        }
+        _ => serde_json::to_string_pretty(&Export {
+            games: records,
+            leaderboard: standings,
+            head_to_head: splits,
+        })?,
+    };
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Run every strategy over the same seeded answers, in normal then difficult mode,
+/// and print a leaderboard for each mode
+pub fn run(args: &Args, tournament: &TournamentArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut final_words = WordList::default();
+    let mut acceptable = WordList::default();
+    game::init_game(args, &mut final_words, &mut acceptable)?;
+
+    let seed = tournament.seed.or(args.seed).unwrap_or_default();
+    let mut answers = final_words.clone();
+    game::init_shuffle(seed, &mut answers);
+    let answers: Vec<&str> = answers.iter().take(tournament.rounds).collect();
+
+    let strategies: Vec<String> = if tournament.strategies.is_empty() {
+        BUILTIN_STRATEGIES
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    } else {
+        tournament.strategies.clone()
+    };
+    // games are told apart by the name of their strategy
+    if let Some(spec) = strategies
+        .iter()
+        .enumerate()
+        .find_map(|(index, spec)| strategies[..index].contains(spec).then_some(spec))
+    {
+        return Err(format!("Strategy {spec} is given more than once").into());
+    }
+    let mut solvers = strategies
+        .iter()
+        .map(|spec| solver::new_solver(spec, &final_words, seed))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut records = Vec::new();
+    let mut all_standings = Vec::new();
+    let mut all_splits = Vec::new();
+    for mode in [Mode::Normal, Mode::Difficult] {
+        for solver in solvers.iter_mut() {
+            for ans in &answers {
+                records.push(play(solver.as_mut(), mode, ans, &acceptable)?);
+            }
+        }
+        let standings = standings(mode, &strategies, &records);
+        print_mode(mode, &strategies, &records, &standings);
+        all_standings.extend(standings);
+        all_splits.extend(splits(mode, &strategies, &records));
+    }
+
+    if let Some(path) = &tournament.export {
+        export(path, &records, &all_standings, &all_splits)?;
+    }
+    Ok(())
+}
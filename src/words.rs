@@ -0,0 +1,148 @@
+use crate::game::MAX_WORD_LENGTH;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// The lowercase ASCII letters of a word, followed by zeros
+type Word = [u8; MAX_WORD_LENGTH];
+
+fn to_word(word: &str) -> Option<Word> {
+    let bytes = word.as_bytes();
+    if bytes.is_empty()
+        || bytes.len() > MAX_WORD_LENGTH
+        || !bytes.iter().all(u8::is_ascii_lowercase)
+    {
+        return None;
+    }
+    let mut padded = [0; MAX_WORD_LENGTH];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    Some(padded)
+}
+
+/// A list of distinct words of the same length, each with a stable index,
+/// and O(1) membership tests
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Set by the first word, 0 while the list is empty
+    word_length: usize,
+    words: Vec<Word>,
+    index: HashMap<Word, usize>,
+}
+
+impl WordList {
+    /// Words appearing more than once keep their first index
+    pub fn from_words<'a>(
+        words: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut list = Self::default();
+        for word in words {
+            if !list.push(word) {
+                return Err(format!("Invalid word {word}").into());
+            }
+        }
+        Ok(list)
+    }
+
+    /// One word per line in any case, ignoring blank lines
+    pub fn read(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut list = Self::default();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if !list.push(&line.to_lowercase()) {
+                return Err(format!("Invalid word {line} in {path}").into());
+            }
+        }
+        Ok(list)
+    }
+
+    /// Add `word` unless it is already there, returning false if it is not a word
+    /// of the same length as the others
+    fn push(&mut self, word: &str) -> bool {
+        if self.word_length != 0 && word.len() != self.word_length {
+            return false;
+        }
+        let Some(padded) = to_word(word) else {
+            return false;
+        };
+        self.word_length = word.len();
+        if !self.index.contains_key(&padded) {
+            self.index.insert(padded, self.words.len());
+            self.words.push(padded);
+        }
+        true
+    }
+
+    fn reindex(&mut self) {
+        self.index = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(idx, word)| (*word, idx))
+            .collect();
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The length of every word, 0 for an empty list
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.index_of(word).is_some()
+    }
+
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        if word.len() != self.word_length {
+            return None;
+        }
+        self.index.get(&to_word(word)?).copied()
+    }
+
+    /// The word at `idx`, which must be below [`WordList::len`]
+    pub fn get(&self, idx: usize) -> &str {
+        // only ASCII letters are ever stored
+        std::str::from_utf8(&self.words[idx][..self.word_length]).unwrap()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + Clone {
+        self.words
+            .iter()
+            .map(|word| std::str::from_utf8(&word[..self.word_length]).unwrap())
+    }
+
+    pub fn is_subset_of(&self, other: &WordList) -> bool {
+        self.words.iter().all(|word| other.index.contains_key(word))
+    }
+
+    /// Shuffle the words. Duplicates were dropped, so this only gives the same order as
+    /// shuffling the lines of the file when the file has none.
+    pub fn shuffle(&mut self, rng: &mut impl Rng) {
+        self.words.shuffle(rng);
+        self.reindex();
+    }
+
+    pub fn sort(&mut self) {
+        self.words.sort_unstable();
+        self.reindex();
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        let word_length = self.word_length;
+        self.words
+            .retain(|word| keep(std::str::from_utf8(&word[..word_length]).unwrap()));
+        self.reindex();
+    }
+}
@@ -0,0 +1,69 @@
+pub mod support;
+
+use support::{TempDir, stdout, wordle};
+
+#[test]
+fn test_replay_analysis() {
+    let dir = TempDir::new("analysis");
+    let final_set = dir.write("final.txt", "grand\nboule\ncrane\nslate\n");
+    let state = dir.write(
+        "state.json",
+        r#"{"total_rounds": 1, "games": [{"answer": "GRAND", "guesses": ["CRANE", "SLATE", "GRAND"]}]}"#,
+    );
+
+    let replay = || {
+        let output = wordle(&["-f", final_set.to_str().unwrap()])
+            .args(["-S", state.to_str().unwrap(), "replay", "--game", "1"])
+            .env("XDG_CACHE_HOME", dir.path())
+            .stdin(std::process::Stdio::null())
+            .output()
+            .expect("failed to execute process");
+        stdout(output)
+    };
+    let output = replay();
+    assert!(output.contains("Game 1: GRAND"), "{output}");
+    // only GRAND is left after CRANE
+    assert!(output.contains("CRANE       4      1"), "{output}");
+    // SLATE ignores the letters CRANE found
+    assert!(output.contains("SLATE*"), "{output}");
+    assert!(output.contains("* not possible"), "{output}");
+
+    // the feedback matrix is cached, and the next run reads it instead of writing it again
+    let matrices: Vec<_> = std::fs::read_dir(dir.path().join("wordle"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_str().unwrap().contains("feedback_matrix."))
+        .collect();
+    assert_eq!(matrices.len(), 1, "{matrices:?}");
+    let modified = || std::fs::metadata(&matrices[0]).unwrap().modified().unwrap();
+    let written = modified();
+    assert_eq!(replay(), output);
+    assert_eq!(modified(), written);
+}
+
+#[test]
+fn test_replay_skips_words_of_another_length() {
+    let dir = TempDir::new("analysis_lengths");
+    let final_set = dir.write("final.txt", "grand\nboule\ncrane\nslate\n");
+    let state = dir.write(
+        "state.json",
+        r#"{"total_rounds": 3, "games": [
+            {"answer": "GRAND", "guesses": ["CRANES", "GRAND"]},
+            {"answer": "GRAND", "guesses": ["CRAN", "GRAND"]},
+            {"answer": "BOULE", "guesses": ["BOULE"]}
+        ]}"#,
+    );
+    let output = wordle(&["-f", final_set.to_str().unwrap()])
+        .args(["-S", state.to_str().unwrap(), "replay"])
+        .env("XDG_CACHE_HOME", dir.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("failed to execute process");
+    assert!(output.status.success());
+    let error = String::from_utf8(output.stderr.clone()).unwrap();
+    assert!(error.contains("Game 1 is skipped"), "{error}");
+    assert!(error.contains("Game 2 is skipped"), "{error}");
+    let output = stdout(output);
+    assert!(!output.contains("Game 1: GRAND"), "{output}");
+    assert!(output.contains("Game 3: BOULE"), "{output}");
+}
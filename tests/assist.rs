@@ -0,0 +1,32 @@
+pub mod support;
+
+use support::{TempDir, spawn, stdout, wordle};
+
+/// Run `assist` over the final words SHEEP, STEEP and GEESE, feeding it `input`
+fn assist(name: &str, input: &str) -> String {
+    let dir = TempDir::new(name);
+    let final_set = dir.write("final.txt", "sheep\nsteep\ngeese\n");
+    let mut command = wordle(&["-f", final_set.to_str().unwrap(), "assist"]);
+    command.env("XDG_CACHE_HOME", dir.path());
+    stdout(spawn(&mut command, input).wait_with_output().unwrap())
+}
+
+#[test]
+fn test_assist_narrows_candidates() {
+    let output = assist("assist", "eerie YYXXX\nsteep GGGGX\nsheep GGGGG\n");
+    let lines: Vec<&str> = output.lines().collect();
+
+    // the third E is grey, but words with two E's still match
+    assert!(lines.contains(&"Candidates: 2 (SHEEP, STEEP)"), "{output}");
+    assert!(output.contains("No word matches this feedback"), "{output}");
+    assert_eq!(lines.last(), Some(&"Solved: SHEEP"), "{output}");
+}
+
+#[test]
+fn test_assist_reads_emoji_feedback() {
+    let output = assist("assist_emoji", "eerie 🟨🟨⬛⬛⬛\nsheep 🟩⬛🟩🟩🟩\n");
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines.contains(&"Candidates: 2 (SHEEP, STEEP)"), "{output}");
+    // only the H of SHEEP is grey against STEEP
+    assert!(lines.contains(&"Candidates: 1 (STEEP)"), "{output}");
+}
@@ -0,0 +1,34 @@
+pub mod support;
+
+use support::{run, stdout};
+
+/// A bot that opens with CRANE, then always guesses SLATE
+const FIXED_BOT: &str = r#"while read line; do
+    case "$line" in
+        START*) echo crane ;;
+        FEEDBACK*|INVALID) echo slate ;;
+        END) exit 0 ;;
+    esac
+done"#;
+
+#[test]
+fn test_bot_plays_given_word() {
+    let output = stdout(run(&["--bot", FIXED_BOT, "-w", "slate"], ""));
+    assert!(output.contains("CORRECT 2"), "{output}");
+    assert!(output.contains("1 0 2.00"), "{output}");
+}
+
+#[test]
+fn test_bot_plays_several_rounds() {
+    let output = stdout(run(&["--bot", FIXED_BOT, "--rounds", "3"], ""));
+    // the first answers are ABACK, ABASE and ABATE, which the bot never finds
+    assert_eq!(output.matches("FAILED").count(), 3, "{output}");
+    assert!(output.contains("0 3 0.00"), "{output}");
+}
+
+#[test]
+fn test_bot_quitting_abandons_game() {
+    let output = stdout(run(&["-r", "--bot", "read line; echo crane"], ""));
+    assert_eq!(output.lines().nth(1), Some("ABANDONED GRAND"), "{output}");
+    assert!(output.ends_with("0 0 0.00\nCRANE 1\n"), "{output}");
+}
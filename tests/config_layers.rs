@@ -0,0 +1,96 @@
+pub mod support;
+
+use support::{TempDir, run, stderr, stdout, wordle};
+
+fn print_config(arguments: &[&str], envs: &[(&str, &str)]) -> String {
+    let output = wordle(arguments)
+        .arg("--print-config")
+        // keep the user config of whoever runs the tests out of the way
+        .env("XDG_CONFIG_HOME", "tests/data/no_such_dir")
+        .envs(envs.iter().copied())
+        .output()
+        .expect("failed to execute process");
+    stdout(output)
+}
+
+#[test]
+fn test_config_precedence_and_provenance() {
+    let output = print_config(
+        &[
+            "-c",
+            "tests/cases/08_02_config_override.config.json",
+            "-r",
+            "-s",
+            "3",
+        ],
+        &[("WORDLE_DAY", "7"), ("WORDLE_SEED", "42")],
+    );
+    let config_source = "(--config tests/cases/08_02_config_override.config.json)";
+    // the config file beats the defaults
+    assert!(output.contains(&format!("stats = true {config_source}")));
+    // the environment beats the config file
+    assert!(output.contains("day = 7 (environment WORDLE_DAY)"));
+    // the command line beats the environment
+    assert!(output.contains("seed = 3 (command line)"));
+    assert!(output.contains("players = 1 (default)"));
+}
+
+#[test]
+fn test_invalid_environment_variable() {
+    let output = wordle(&["--print-config"])
+        .env("XDG_CONFIG_HOME", "tests/data/no_such_dir")
+        .env("WORDLE_DAY", "tomorrow")
+        .output()
+        .expect("failed to execute process");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_string_environment_variables() {
+    // values that look like JSON are still strings for string settings
+    let output = print_config(
+        &[],
+        &[
+            ("WORDLE_NAME", "42"),
+            ("WORDLE_INPUT", "true"),
+            ("WORDLE_STATE", "null"),
+        ],
+    );
+    assert!(output.contains("name = \"42\" (environment WORDLE_NAME)"));
+    assert!(output.contains("input = \"true\" (environment WORDLE_INPUT)"));
+    assert!(output.contains("state = \"null\" (environment WORDLE_STATE)"));
+}
+
+#[test]
+fn test_toml_and_yaml_config_files() {
+    let dir = TempDir::new("config");
+    let toml_path = dir.write(
+        "team.toml",
+        "# shared team config\nrandom = true # daily puzzle\nday = 9\n",
+    );
+    let yaml_path = dir.write("team.yaml", "# shared team config\ndifficult: true\n");
+    let typo_path = dir.write("typo.toml", "difficulty = true\n");
+
+    let output = print_config(&["-c", toml_path.to_str().unwrap()], &[]);
+    assert!(output.contains("day = 9 (--config"));
+    let output = print_config(&["-c", yaml_path.to_str().unwrap()], &[]);
+    assert!(output.contains("difficult = true (--config"));
+
+    // unknown keys are rejected, naming both the key and the file
+    let error = stderr(run(
+        &["--print-config", "-c", typo_path.to_str().unwrap()],
+        "",
+    ));
+    assert!(error.contains("difficulty") && error.contains("typo.toml"));
+}
+
+#[test]
+fn test_language_from_locale() {
+    let output = print_config(
+        &[],
+        &[("LC_ALL", ""), ("LC_MESSAGES", ""), ("LANG", "zh_CN.UTF-8")],
+    );
+    assert!(output.contains("lang = \"zh\" (locale LANG)"));
+    let output = print_config(&["--lang", "en"], &[("LANG", "zh_CN.UTF-8")]);
+    assert!(output.contains("lang = \"en\" (command line)"));
+}
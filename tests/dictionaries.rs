@@ -0,0 +1,63 @@
+pub mod support;
+
+use support::{run, stderr, stdout};
+
+#[test]
+fn test_list_dictionaries() {
+    let output = stdout(run(&["--list-dictionaries"], ""));
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(
+        lines[1].starts_with("nyt          2315      12972  "),
+        "{output}"
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("expanded    12972      12972  "))
+    );
+    assert!(lines.iter().any(|line| line.starts_with("kids ")));
+    assert!(lines.iter().any(|line| line.starts_with("four ")));
+    assert!(lines.iter().any(|line| line.starts_with("six ")));
+}
+
+#[test]
+fn test_kids_dictionary_answers() {
+    // every guess is still checked against the acceptable words
+    let words = "crane\nslate\nmoist\nplumb\nfight\nvowel\n";
+    let output = stdout(run(&["--dictionary", "kids", "-r", "-d", "3"], words));
+    let answer = output
+        .lines()
+        .last()
+        .unwrap()
+        .strip_prefix("FAILED ")
+        .unwrap();
+    let kids = std::fs::read_to_string("dictionaries/kids-final.txt").unwrap();
+    assert!(
+        kids.lines().any(|word| word.eq_ignore_ascii_case(answer)),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_dictionaries_of_other_lengths() {
+    // a word of another length is not acceptable
+    let output = stdout(run(
+        &["--dictionary", "six", "-w", "garden"],
+        "crane\nforest\ngarden\n",
+    ));
+    assert_eq!(
+        output.lines().collect::<Vec<_>>()[..2],
+        ["INVALID", "RRGYRR XXXXYRXXXXXXXXRXXGRRXXXXXX"]
+    );
+    assert!(output.ends_with("CORRECT 2\n"), "{output}");
+
+    let output = stdout(run(&["--dictionary", "four", "-w", "cake"], "lake\ncake\n"));
+    assert!(output.starts_with("RGGG "), "{output}");
+    assert!(output.ends_with("CORRECT 2\n"), "{output}");
+}
+
+#[test]
+fn test_unknown_dictionary() {
+    let error = stderr(run(&["--dictionary", "klingon", "-w", "crane"], "crane\n"));
+    assert!(error.contains("Unknown dictionary klingon, expected one of nyt, expanded, kids"));
+}
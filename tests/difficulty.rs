@@ -0,0 +1,72 @@
+pub mod support;
+
+use std::path::Path;
+use support::{TempDir, read_json, run, spawn, stderr, stdout, wordle};
+
+fn failed_answer(args: &[&str], cache: &Path) -> String {
+    let mut command = wordle(args);
+    command.env("XDG_CACHE_HOME", cache);
+    let output = stdout(
+        spawn(&mut command, &"xylyl\n".repeat(6))
+            .wait_with_output()
+            .unwrap(),
+    );
+    let answer = output
+        .lines()
+        .find_map(|line| line.strip_prefix("FAILED "))
+        .unwrap_or_else(|| panic!("no FAILED line in {output}"));
+    answer.to_lowercase()
+}
+
+#[test]
+fn test_difficulty_restricts_answers() {
+    let cache = TempDir::new("cache");
+
+    let hard = failed_answer(&["-r", "--difficulty", "hard"], cache.path());
+    let ratings_file = cache.path().join("wordle").join("nyt.ratings.json");
+    let mut ratings = read_json(&ratings_file);
+    assert_eq!(ratings["data"][&hard]["difficulty"], "hard");
+    let easy = failed_answer(&["-r", "--difficulty", "easy", "-d", "2"], cache.path());
+    assert_eq!(ratings["data"][&easy]["difficulty"], "easy");
+
+    // the next run reads the cached ratings: with only BOULE rated easy, it is the answer
+    for (_, rating) in ratings["data"].as_object_mut().unwrap() {
+        rating["difficulty"] = "hard".into();
+    }
+    ratings["data"]["boule"]["difficulty"] = "easy".into();
+    std::fs::write(&ratings_file, ratings.to_string()).unwrap();
+    let answer = failed_answer(&["-r", "--difficulty", "easy", "-d", "5"], cache.path());
+    assert_eq!(answer, "boule");
+}
+
+#[test]
+fn test_corrupted_ratings_are_computed_again() {
+    let cache = TempDir::new("corrupted_cache");
+    let ratings_file = cache.path().join("wordle").join("nyt.ratings.json");
+    std::fs::create_dir_all(ratings_file.parent().unwrap()).unwrap();
+    std::fs::write(&ratings_file, "{\"hash\": ").unwrap();
+
+    let hard = failed_answer(&["-r", "--difficulty", "hard"], cache.path());
+    assert_eq!(
+        read_json(&ratings_file)["data"][&hard]["difficulty"],
+        "hard"
+    );
+}
+
+#[test]
+fn test_empty_difficulty_band() {
+    // too few words to rate any of them hard
+    let dir = TempDir::new("empty_band");
+    let final_set = dir.write("final.txt", "crane\ngrand\n");
+    let error = stderr(run(
+        &[
+            "-r",
+            "-f",
+            final_set.to_str().unwrap(),
+            "--difficulty",
+            "hard",
+        ],
+        "crane\n",
+    ));
+    assert!(error.contains("No final word is rated hard"), "{error}");
+}
@@ -0,0 +1,42 @@
+pub mod support;
+
+use ntest::timeout;
+use support::{TempDir, read_json, run, stdout};
+
+#[test]
+#[timeout(10000)]
+fn test_end_of_input_abandons_game() {
+    let dir = TempDir::new("eof");
+    let state = dir.path().join("state.json");
+    let state_arg = state.to_str().unwrap();
+
+    let output = stdout(run(&["-r", "-t", "-S", state_arg], "crane\nslate"));
+    assert!(
+        output.ends_with("ABANDONED GRAND\n0 0 0.00\nCRANE 1 SLATE 1\n"),
+        "{output}"
+    );
+
+    assert_eq!(
+        read_json(&state),
+        serde_json::json!({
+            "total_rounds": 1,
+            "games": [{ "answer": "GRAND", "guesses": ["CRANE", "SLATE"], "abandoned": true }],
+        })
+    );
+    // an abandoned game is neither won nor lost when loaded again
+    assert_eq!(
+        stdout(run(&["-S", state_arg, "stats"], "")),
+        "0 0 0.00\nCRANE 1 SLATE 1\n"
+    );
+}
+
+#[test]
+#[timeout(10000)]
+fn test_end_of_input_before_a_guess() {
+    // nothing is played, or recorded
+    for args in [&["-r", "-t"][..], &[], &["-w", "crane"]] {
+        assert_eq!(stdout(run(args, "")), "");
+    }
+    // after a finished game, the end of the input ends the session
+    assert!(stdout(run(&["-r"], "crane\ngrand\nY\n")).ends_with("CORRECT 2\n"));
+}
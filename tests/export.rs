@@ -0,0 +1,56 @@
+pub mod support;
+
+use pretty_assertions::assert_eq;
+use std::path::{Path, PathBuf};
+use support::{TempDir, run, stdout};
+
+/// A state file with a win in 2, a loss, a win in 4 in difficult mode on 2000-02-29,
+/// and a game abandoned after 1 guess
+fn state_file(dir: &TempDir) -> PathBuf {
+    let state = serde_json::json!({
+        "total_rounds": 4,
+        "games": [
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"] },
+            { "answer": "BOULE", "guesses": ["CRANE", "SLATE", "MOIST", "PLUMB", "FIGHT", "VOWEL"] },
+            {
+                "answer": "SHEEP",
+                "guesses": ["CRANE", "STEEP", "SWEEP", "SHEEP"],
+                "difficult": true,
+                "played_at": 951_868_799_999u64,
+            },
+            { "answer": "PLUMB", "guesses": ["CRANE"], "abandoned": true },
+        ],
+    });
+    dir.write("state.json", &state.to_string())
+}
+
+fn export(state: &Path, args: &[&str]) -> String {
+    let mut arguments = vec!["-S", state.to_str().unwrap(), "export"];
+    arguments.extend(args);
+    stdout(run(&arguments, ""))
+}
+
+#[test]
+fn test_export_csv_and_markdown() {
+    let dir = TempDir::new("export");
+    let state = state_file(&dir);
+    assert_eq!(
+        export(&state, &["--format", "csv"]),
+        "game,answer,guesses,attempts,win,abandoned,mode,date,day,feedback\n\
+         1,GRAND,CRANE GRAND,2,true,false,normal,,,\n\
+         2,BOULE,CRANE SLATE MOIST PLUMB FIGHT VOWEL,6,false,false,normal,,,\n\
+         3,SHEEP,CRANE STEEP SWEEP SHEEP,4,true,false,difficult,2000-02-29,,\n\
+         4,PLUMB,CRANE,1,false,true,normal,,,\n"
+    );
+
+    // the format follows the extension of the output file
+    let report = state.with_extension("md");
+    export(&state, &["-o", report.to_str().unwrap()]);
+    let report_content = std::fs::read_to_string(&report).unwrap();
+    assert!(report_content.contains("| 4 | 2 | 1 | 1 | 66.7% | 3.00 |\n"));
+    assert!(report_content.contains("| 2 | 1 | ████████████████████ |\n"));
+    assert!(report_content.contains("| 4 | 1 | ████████████████████ |\n"));
+    assert!(report_content.contains("| 6 | 0 |  |\n"));
+    assert!(report_content.contains("| X | 1 | ████████████████████ |\n"));
+    assert!(report_content.contains("| CRANE | 4 |\n"));
+}
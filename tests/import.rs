@@ -0,0 +1,115 @@
+pub mod support;
+
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use std::process::Output;
+use support::{TempDir, read_json, stderr, stdout};
+
+const SHARE: &str = "Wordle 1,234 3/6*
+
+⬛🟨⬛⬛⬛
+🟩🟩⬛🟨⬛
+🟩🟩🟩🟩🟩
+https://www.nytimes.com/games/wordle
+
+Wordle 1,235 X/6
+
+⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛
+🟩⬛⬛⬛⬛
+";
+
+fn run(state: &Path, args: &[&str]) -> Output {
+    let mut arguments = vec!["-S", state.to_str().unwrap()];
+    arguments.extend(args);
+    support::run(&arguments, "")
+}
+
+#[test]
+fn test_import_share_text() {
+    let dir = TempDir::new("import_share");
+    let state = dir.path().join("state.json");
+    let share = dir.write("share.txt", SHARE);
+
+    let output = stdout(run(&state, &["import", share.to_str().unwrap()]));
+    assert_eq!(
+        output,
+        "Imported 2 games, skipped 0 already in the state file\n"
+    );
+    let data = read_json(&state);
+    assert_eq!(data["total_rounds"], 2);
+    assert_eq!(data["games"][0]["day"], 1234);
+    // the `*` marks a game played in difficult mode
+    assert_eq!(data["games"][0]["difficult"], true);
+    assert_eq!(data["games"][1].get("difficult"), None);
+    assert_eq!(
+        data["games"][0]["feedback"],
+        serde_json::json!(["RYRRR", "GGRYR", "GGGGG"])
+    );
+
+    // pasting the same results again adds nothing
+    let output = stdout(run(&state, &["import", share.to_str().unwrap()]));
+    assert_eq!(
+        output,
+        "Imported 0 games, skipped 2 already in the state file\n"
+    );
+
+    // the attempts of a game are known even though its guesses are not
+    assert_eq!(stdout(run(&state, &["stats"])), "1 1 3.00\n\n");
+}
+
+#[test]
+fn test_import_exported_csv() {
+    let dir = TempDir::new("import_csv");
+    let state = dir.path().join("state.json");
+    let csv = dir.write(
+        "games.csv",
+        "game,answer,guesses,attempts,win,abandoned,mode,date,day,feedback\n\
+         1,GRAND,CRANE GRAND,2,true,false,difficult,,,\n\
+         2,,,3,true,false,normal,,1234,RYRRR GGRYR GGGGG\n\
+         3,PLUMB,SLATE,1,false,true,normal,,,\n",
+    );
+    let output = stdout(run(&state, &["import", csv.to_str().unwrap()]));
+    assert_eq!(
+        output,
+        "Imported 3 games, skipped 0 already in the state file\n"
+    );
+    // the abandoned game is not counted as lost
+    assert_eq!(
+        stdout(run(&state, &["stats"])),
+        "2 0 2.50\nCRANE 1 GRAND 1 SLATE 1\n"
+    );
+    assert_eq!(read_json(&state)["games"][2]["abandoned"], true);
+    let exported = stdout(run(&state, &["export", "--format", "csv"]));
+    assert_eq!(exported, std::fs::read_to_string(&csv).unwrap());
+}
+
+#[test]
+fn test_import_inconsistent_share() {
+    let dir = TempDir::new("import_invalid");
+    let state = dir.path().join("state.json");
+    let share = dir.write("share.txt", "Wordle 1,236 4/6\n\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩\n");
+    let error = stderr(run(&state, &["import", share.to_str().unwrap()]));
+    assert!(error.contains("expected 4 rows, found 2"), "{error}");
+    assert!(!state.exists());
+}
+
+#[test]
+fn test_import_csv_with_invalid_words() {
+    let dir = TempDir::new("import_invalid_words");
+    let state = dir.path().join("state.json");
+    for (name, content) in [
+        ("longer.csv", "answer,guesses\ngrand,cranes grand\n"),
+        ("shorter.csv", "answer,guesses\ngrand,cran grand\n"),
+        ("answer.csv", "answer,guesses\ngrands,crane grand\n"),
+        ("letters.csv", "answer,guesses\ngrand,cr4ne grand\n"),
+    ] {
+        let csv = dir.write(name, content);
+        let error = stderr(run(&state, &["import", csv.to_str().unwrap()]));
+        assert!(error.contains("Line 2: invalid word"), "{name}: {error}");
+        assert!(!state.exists());
+    }
+}
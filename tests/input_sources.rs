@@ -0,0 +1,16 @@
+pub mod support;
+
+use support::{run, stdout};
+
+#[test]
+fn test_read_guesses_from_file() {
+    let output = stdout(run(&["--input", "tests/cases/01_01_single_game.in"], ""));
+    let answer = std::fs::read_to_string("tests/cases/01_01_single_game.ans").unwrap();
+    assert_eq!(output.trim(), answer.trim());
+}
+
+#[test]
+fn test_missing_input_file() {
+    let output = run(&["--input", "tests/data/no_such_file.in"], "");
+    assert!(!output.status.success());
+}
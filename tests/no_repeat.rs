@@ -0,0 +1,49 @@
+pub mod support;
+
+use std::path::PathBuf;
+use support::{TempDir, run};
+
+/// A state file where the given answers were found in one guess
+fn state_file(dir: &TempDir, answers: &[&str]) -> PathBuf {
+    let games: Vec<_> = answers
+        .iter()
+        .map(|answer| serde_json::json!({ "answer": answer, "guesses": [answer] }))
+        .collect();
+    let state = serde_json::json!({ "total_rounds": games.len(), "games": games });
+    dir.write("state.json", &state.to_string())
+}
+
+#[test]
+fn test_no_repeat_skips_played_answer() {
+    // with the default seed, day 1 is GRAND and day 2 is BOULE
+    let dir = TempDir::new("no_repeat");
+    let state = state_file(&dir, &["GRAND"]);
+    let output = run(
+        &["-r", "--no-repeat", "-S", state.to_str().unwrap()],
+        "boule\nN\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("CORRECT 1"), "{stdout}");
+}
+
+#[test]
+fn test_no_repeat_warns_when_exhausted() {
+    let dir = TempDir::new("exhausted");
+    let state = state_file(&dir, &["GRAND", "BOULE"]);
+    let final_set = dir.write("final.txt", "grand\nboule\n");
+    let output = run(
+        &[
+            "-r",
+            "--no-repeat",
+            "-S",
+            state.to_str().unwrap(),
+            "-f",
+            final_set.to_str().unwrap(),
+        ],
+        "grand\nboule\nN\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stdout.contains("CORRECT"), "{stdout}");
+    assert!(stderr.contains("Every answer has been played"), "{stderr}");
+}
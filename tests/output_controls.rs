@@ -0,0 +1,77 @@
+pub mod support;
+
+use support::{spawn, stdout, wordle};
+
+fn run(arguments: &[&str], input: &str) -> String {
+    let mut command = wordle(arguments);
+    command.env_remove("NO_COLOR");
+    stdout(spawn(&mut command, input).wait_with_output().unwrap())
+}
+
+#[test]
+fn test_history_layout_without_tty() {
+    let output = run(&["-w", "slate", "--layout", "history"], "crane\nslate\n");
+    let rows: Vec<&str> = output.lines().collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0], rows[1]);
+    assert!(rows[2].starts_with("GGGGG "));
+    assert_eq!(rows[3], "CORRECT 2");
+}
+
+#[test]
+fn test_colours_forced_on_codes() {
+    let output = run(&["-w", "slate", "--color", "always"], "slate\n");
+    assert!(output.starts_with("\x1b["));
+    assert!(output.contains("CORRECT 1"));
+}
+
+#[test]
+fn test_letters_keep_protocol_messages_without_tty() {
+    let output = run(&["-w", "slate", "--format", "letters"], "crane\nslate\n");
+    let rows: Vec<&str> = output.lines().collect();
+    assert!(rows[1].starts_with("SLATE "), "{output}");
+    assert_eq!(rows[2], "CORRECT 2");
+}
+
+#[test]
+fn test_json_renderer() {
+    let output = run(&["-w", "slate", "--renderer", "json"], "slate\n");
+    let row: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    assert_eq!(row["guess"], "SLATE");
+    assert_eq!(row["result"], "GGGGG");
+}
+
+#[test]
+fn test_json_renderer_prints_only_json() {
+    let output = run(
+        &["-w", "slate", "--renderer", "json", "-t"],
+        "zzzzz\ncrane\nslate\n",
+    );
+    let rows: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(rows[0], serde_json::json!({ "message": "INVALID" }));
+    assert_eq!(
+        rows[3],
+        serde_json::json!({ "outcome": "won", "attempts": 2, "answer": "SLATE" })
+    );
+    assert_eq!(rows[4]["wins"], 1);
+    assert_eq!(
+        rows[4]["top_words"],
+        serde_json::json!([["CRANE", 1], ["SLATE", 1]])
+    );
+}
+
+#[test]
+fn test_screen_reader_follows_language() {
+    let output = run(&["-w", "slate", "--renderer", "screen-reader"], "crane\n");
+    assert!(output.starts_with("Guess 1: C not in the word, R not in the word, A correct,"));
+    assert!(output.contains("Correct letters: A, E. Wrong place: none."));
+    let output = run(
+        &["-w", "slate", "--renderer", "screen-reader", "--lang", "zh"],
+        "crane\n",
+    );
+    assert!(output.starts_with("第 1 次猜测：C 不在单词中、R 不在单词中、A 正确、"));
+    assert!(output.contains("正确的字母：A、E。位置错误：无。"));
+}
@@ -0,0 +1,78 @@
+pub mod support;
+
+use ntest::timeout;
+use std::net::TcpListener;
+use support::{TempDir, read_json, spawn, stdout, wordle};
+
+#[test]
+#[timeout(10000)]
+fn test_race_over_localhost() {
+    // find a free port for the host
+    let addr = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    };
+    let dir = TempDir::new("race");
+    let state_file = dir.path().join("state.json");
+
+    let host = spawn(
+        &mut wordle(&["--host", &addr, "-w", "crane"]),
+        "slate\ncrane\n",
+    );
+    let client = spawn(
+        &mut wordle(&[
+            "--join",
+            &addr,
+            "--name",
+            "alice",
+            "--state",
+            state_file.to_str().unwrap(),
+        ]),
+        "crane\n",
+    );
+
+    let host_output = stdout(host.wait_with_output().unwrap());
+    let client_output = stdout(client.wait_with_output().unwrap());
+
+    assert!(host_output.contains("CORRECT 2"), "{host_output}");
+    assert!(host_output.contains("WINNER alice 1"), "{host_output}");
+    assert!(client_output.starts_with("GGGGG "), "{client_output}");
+    assert!(client_output.contains("CORRECT 1"), "{client_output}");
+    assert!(client_output.contains("WINNER alice 1"), "{client_output}");
+
+    // the client records the race in its own state file
+    assert_eq!(read_json(&state_file)["games"][0]["answer"], "CRANE");
+}
+
+#[test]
+#[timeout(10000)]
+fn test_race_without_guesses_is_not_recorded() {
+    let addr = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    };
+    let dir = TempDir::new("race_abandoned");
+    let state_file = dir.path().join("state.json");
+
+    let host = spawn(&mut wordle(&["--host", &addr, "-w", "crane"]), "crane\n");
+    // the input of the client ends before its first guess
+    let client = spawn(
+        &mut wordle(&[
+            "--join",
+            &addr,
+            "--name",
+            "alice",
+            "--state",
+            state_file.to_str().unwrap(),
+        ]),
+        "",
+    );
+
+    let host_output = stdout(host.wait_with_output().unwrap());
+    client.wait_with_output().unwrap();
+    assert!(host_output.contains("CORRECT 1"), "{host_output}");
+
+    let data = read_json(&state_file);
+    assert_eq!(data["games"], serde_json::json!([]));
+    assert_eq!(data["total_rounds"], 0);
+}
@@ -0,0 +1,118 @@
+pub mod support;
+
+use ntest::timeout;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use support::{TempDir, read_json, wordle};
+
+/// A state file with GRAND in progress after CRANE and SLATE
+fn state_file(dir: &TempDir) -> std::path::PathBuf {
+    let data = serde_json::json!({
+        "total_rounds": 0,
+        "games": [],
+        "unfinished": { "answer": "GRAND", "difficult": false, "guesses": ["CRANE", "SLATE"] },
+    });
+    dir.write("state.json", &data.to_string())
+}
+
+fn run(args: &[&str], state: &Path, input: &str) -> Output {
+    let mut arguments = args.to_vec();
+    arguments.extend(["-S", state.to_str().unwrap()]);
+    support::run(&arguments, input)
+}
+
+#[test]
+fn test_resume_unfinished_game() {
+    let dir = TempDir::new("resume_same");
+    let state = state_file(&dir);
+    // without a player to ask, the game is resumed because it has the same answer
+    let output = run(&["-w", "grand"], &state, "moist\ngrand\n");
+    assert!(output.status.success());
+    // the two saved guesses count as attempts
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .ends_with("CORRECT 4\n")
+    );
+    let data = read_json(&state);
+    assert_eq!(data["unfinished"], serde_json::Value::Null);
+    assert_eq!(
+        data["games"][0],
+        serde_json::json!({ "answer": "GRAND", "guesses": ["CRANE", "SLATE", "MOIST", "GRAND"] })
+    );
+}
+
+#[test]
+fn test_resumed_game_keeps_its_mode() {
+    let dir = TempDir::new("resume_mode");
+    let data = serde_json::json!({
+        "total_rounds": 0,
+        "games": [],
+        "unfinished": { "answer": "GRAND", "difficult": true, "guesses": ["CRANE"] },
+    });
+    let state = dir.write("state.json", &data.to_string());
+    // BOULE ignores the letters CRANE found, which difficult mode rejects
+    let output = run(&["-w", "grand"], &state, "boule\ngrand\n");
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains("INVALID"), "{output}");
+    assert!(output.ends_with("CORRECT 2\n"), "{output}");
+    let data = read_json(&state);
+    assert_eq!(data["games"][0]["difficult"], true);
+}
+
+#[test]
+fn test_other_answer_keeps_unfinished_game() {
+    let dir = TempDir::new("resume_other");
+    let state = state_file(&dir);
+    // the first line is the first guess, and the answer given is played
+    let output = run(&["-w", "boule"], &state, "boule\n");
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .ends_with("CORRECT 1\n")
+    );
+    let data = read_json(&state);
+    assert_eq!(
+        data["unfinished"],
+        serde_json::json!({ "answer": "GRAND", "difficult": false, "guesses": ["CRANE", "SLATE"] })
+    );
+    assert_eq!(data["games"][0]["answer"], "BOULE");
+    assert_eq!(data["total_rounds"], 1);
+}
+
+#[test]
+#[timeout(20000)]
+fn test_interrupt_saves_progress() {
+    let dir = TempDir::new("resume_int");
+    let state = dir.path().join("state.json");
+    let mut child = wordle(&["-r", "-S", state.to_str().unwrap()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute process");
+    let mut input = child.stdin.take().unwrap();
+    input.write_all(b"crane\nslate\n").unwrap();
+    // wait for the second guess to be saved
+    while !std::fs::read_to_string(&state).is_ok_and(|data| data.contains("SLATE")) {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let kill = std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(kill.success());
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(130));
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Interrupted")
+    );
+    assert_eq!(
+        read_json(&state)["unfinished"],
+        serde_json::json!({ "answer": "GRAND", "difficult": false, "guesses": ["CRANE", "SLATE"] })
+    );
+    drop(input);
+}
@@ -0,0 +1,123 @@
+pub mod support;
+
+use pretty_assertions::assert_eq;
+use support::{TempDir, read_json, run, stdout};
+
+#[test]
+fn test_state_merge() {
+    let dir = TempDir::new("merge");
+    let laptop = dir.write(
+        "laptop.json",
+        &serde_json::json!({ "total_rounds": 3, "games": [
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"] },
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"] },
+            { "answer": "", "guesses": [], "feedback": ["GGGGG"], "day": 1234 },
+        ] })
+        .to_string(),
+    );
+    let server = dir.write(
+        "server.json",
+        &serde_json::json!({ "total_rounds": 99, "games": [
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"] },
+            { "answer": "BOULE", "guesses": ["CRANE", "BOULE"] },
+            { "answer": "", "guesses": [], "feedback": ["RRRRR", "GGGGG"], "day": 1234 },
+        ] })
+        .to_string(),
+    );
+    let merged = dir.write("merged.json", "{}");
+
+    let output = stdout(run(
+        &[
+            "state",
+            "merge",
+            laptop.to_str().unwrap(),
+            server.to_str().unwrap(),
+            "-o",
+            merged.to_str().unwrap(),
+        ],
+        "",
+    ));
+    let (laptop_name, server_name) = (laptop.display(), server.display());
+    assert_eq!(
+        output,
+        format!(
+            "Merged 5 games from 2 files into {}\n\
+             Conflict: GRAND (CRANE GRAND): 2 in {laptop_name}, 1 in {server_name}, kept 2\n\
+             Conflict: day 1234 was played differently: GGGGG, RRRRR GGGGG\n",
+            merged.display()
+        )
+    );
+
+    let data = read_json(&merged);
+    assert_eq!(data["total_rounds"], 5);
+    let answers: Vec<&str> = data["games"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|game| game["answer"].as_str().unwrap())
+        .collect();
+    assert_eq!(answers, ["GRAND", "GRAND", "", "BOULE", ""]);
+}
+
+#[test]
+fn test_merge_timestamped_games() {
+    let dir = TempDir::new("merge_timestamps");
+    // GRAND was played twice on the laptop, and the server has a copy of the first game
+    let laptop = dir.write(
+        "laptop.json",
+        &serde_json::json!({ "total_rounds": 2, "games": [
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"], "played_at": 1000 },
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"], "played_at": 2000 },
+        ] })
+        .to_string(),
+    );
+    let server = dir.write(
+        "server.json",
+        &serde_json::json!({ "total_rounds": 2, "games": [
+            { "answer": "GRAND", "guesses": ["CRANE", "GRAND"], "played_at": 1000 },
+            { "answer": "BOULE", "guesses": ["CRANE", "BOULE"], "played_at": 3000 },
+        ] })
+        .to_string(),
+    );
+    let merged = dir.path().join("merged.json");
+
+    let output = stdout(run(
+        &[
+            "state",
+            "merge",
+            laptop.to_str().unwrap(),
+            server.to_str().unwrap(),
+            "-o",
+            merged.to_str().unwrap(),
+        ],
+        "",
+    ));
+    // no conflict, as the timestamps tell the games apart
+    assert_eq!(
+        output,
+        format!("Merged 3 games from 2 files into {}\n", merged.display())
+    );
+    let played_at: Vec<u64> = read_json(&merged)["games"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|game| game["played_at"].as_u64().unwrap())
+        .collect();
+    assert_eq!(played_at, [1000, 2000, 3000]);
+}
+
+#[test]
+fn test_timestamps_are_recorded() {
+    let dir = TempDir::new("timestamps");
+    let state = dir.path().join("state.json");
+    let state = state.to_str().unwrap();
+    stdout(run(&["-w", "grand", "-S", state], "grand\n"));
+    stdout(run(
+        &["-w", "grand", "-S", state, "--timestamps"],
+        "grand\n",
+    ));
+
+    let games = read_json(std::path::Path::new(state))["games"].clone();
+    assert!(games[0].get("played_at").is_none(), "{games}");
+    assert!(games[1]["played_at"].as_u64().unwrap() > 0, "{games}");
+}
@@ -0,0 +1,137 @@
+pub mod support;
+
+use ntest::timeout;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, Stdio};
+use support::{TempDir, read_json, wordle};
+
+fn spawn(args: &[&str], state: &Path) -> Child {
+    wordle(args)
+        .arg("-S")
+        .arg(state)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("failed to execute process")
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    names
+}
+
+fn answers(path: &Path) -> Vec<String> {
+    read_json(path)["games"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|game| game["answer"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+#[timeout(20000)]
+fn test_concurrent_sessions_keep_every_game() {
+    let dir = TempDir::new("concurrent");
+    let state = dir.path().join("state.json");
+
+    // the first session saves its first game, then keeps going
+    let mut first = spawn(&["-r", "--backups"], &state);
+    let mut first_input = first.stdin.take().unwrap();
+    first_input.write_all(b"crane\ngrand\n").unwrap();
+    // the game in progress is saved too, so wait for the finished one
+    while !std::fs::read_to_string(&state)
+        .ok()
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .is_some_and(|data| data["total_rounds"] == 1)
+    {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    // another session saves a game in between
+    let mut second = spawn(&["-w", "sheep", "--backups"], &state);
+    second.stdin.take().unwrap().write_all(b"sheep\n").unwrap();
+    assert!(second.wait().unwrap().success());
+    assert_eq!(answers(&state), ["GRAND", "SHEEP"]);
+
+    // and the game saved last by the first session is added after it
+    first_input.write_all(b"Y\ncrane\nboule\nN\n").unwrap();
+    drop(first_input);
+    assert!(first.wait().unwrap().success());
+    assert_eq!(answers(&state), ["GRAND", "SHEEP", "BOULE"]);
+    assert_eq!(read_json(&state)["total_rounds"], 3);
+
+    // each session kept a backup of the file before its first game, the oldest one
+    // only having the first game in progress, and no lock or temporary file is left
+    assert_eq!(answers(&dir.path().join("state.json.1")), ["GRAND"]);
+    assert!(answers(&dir.path().join("state.json.2")).is_empty());
+    assert_eq!(
+        file_names(dir.path()),
+        ["state.json", "state.json.1", "state.json.2"]
+    );
+}
+
+#[test]
+fn test_backups_rotate() {
+    let dir = TempDir::new("backups");
+    let state = dir.path().join("state.json");
+    for answer in ["grand", "sheep", "boule", "crane", "slate"] {
+        let mut session = spawn(&["-w", answer, "--backups"], &state);
+        session
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{answer}\n").as_bytes())
+            .unwrap();
+        assert!(session.wait().unwrap().success());
+    }
+    assert_eq!(answers(&state).len(), 5);
+    assert_eq!(answers(&dir.path().join("state.json.1")).len(), 4);
+    assert_eq!(answers(&dir.path().join("state.json.3")).len(), 2);
+    assert!(!dir.path().join("state.json.4").exists());
+}
+
+#[test]
+fn test_no_backups_by_default() {
+    let dir = TempDir::new("no_backups");
+    let state = dir.path().join("state.json");
+    for answer in ["grand", "sheep"] {
+        let mut session = spawn(&["-w", answer], &state);
+        session
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{answer}\n").as_bytes())
+            .unwrap();
+        assert!(session.wait().unwrap().success());
+    }
+    assert_eq!(answers(&state), ["GRAND", "SHEEP"]);
+    assert_eq!(file_names(dir.path()), ["state.json"]);
+}
+
+#[test]
+#[timeout(20000)]
+fn test_invalid_state_is_kept() {
+    let dir = TempDir::new("invalid_state");
+    let state = dir.path().join("state.json");
+    let mut session = spawn(&["-w", "grand"], &state);
+    let mut input = session.stdin.take().unwrap();
+    input.write_all(b"crane\n").unwrap();
+    // the game in progress is saved after the first guess
+    while !std::fs::read_to_string(&state).is_ok_and(|data| data.contains("CRANE")) {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    // the file turns invalid before the game is saved again
+    std::fs::write(&state, "{ not json").unwrap();
+    input.write_all(b"grand\n").unwrap();
+    drop(input);
+    assert!(!session.wait().unwrap().success());
+    assert_eq!(std::fs::read_to_string(&state).unwrap(), "{ not json");
+    assert_eq!(file_names(dir.path()), ["state.json"]);
+}
@@ -0,0 +1,67 @@
+pub mod support;
+
+use support::{TempDir, run, stdout};
+
+#[test]
+fn test_play_is_the_default_command() {
+    let input = "crane\ngrand\n";
+    let bare = stdout(run(&["-r"], input));
+    assert_eq!(stdout(run(&["-r", "play"], input)), bare);
+    // the options may also follow the command
+    assert_eq!(stdout(run(&["play", "-r"], input)), bare);
+    assert_eq!(
+        stdout(run(&["play", "-r", "--format", "codes"], input)),
+        stdout(run(&["-r", "--format", "codes"], input))
+    );
+    let letters = stdout(run(
+        &["play", "-w", "slate", "--format", "letters"],
+        "slate
+",
+    ));
+    assert!(letters.starts_with("SLATE "), "{letters}");
+    assert!(bare.ends_with("CORRECT 2\n"));
+}
+
+#[test]
+fn test_stats_and_export() {
+    let dir = TempDir::new("subcommands");
+    let state = dir.path().join("state.json");
+    let state = state.to_str().unwrap();
+    stdout(run(&["-r", "-S", state], "crane\ngrand\n"));
+
+    let stats = stdout(run(&["-S", state, "stats"], ""));
+    assert_eq!(stats, "1 0 2.00\nCRANE 1 GRAND 1\n");
+    assert_eq!(stdout(run(&["stats", "-S", state], "")), stats);
+    let export: serde_json::Value =
+        serde_json::from_str(&stdout(run(&["-S", state, "export"], ""))).unwrap();
+    assert_eq!(
+        export["games"][0]["guesses"],
+        serde_json::json!(["CRANE", "GRAND"])
+    );
+
+    // both need a state file
+    assert!(!run(&["stats"], "").status.success());
+}
+
+#[test]
+fn test_solve() {
+    let output = stdout(run(&["solve", "grand"], ""));
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[lines.len() - 2], "GRAND GGGGG");
+    assert_eq!(
+        *lines.last().unwrap(),
+        format!("Solved in {}", lines.len() - 1)
+    );
+
+    assert!(!run(&["solve", "zzzzz"], "").status.success());
+}
+
+#[test]
+fn test_lists() {
+    let output = stdout(run(&["lists"], ""));
+    assert!(output.starts_with("name "));
+    let kids = stdout(run(&["lists", "kids"], ""));
+    assert_eq!(kids.lines().count(), 112);
+    let acceptable = stdout(run(&["lists", "kids", "--acceptable"], ""));
+    assert_eq!(acceptable.lines().count(), 12972);
+}
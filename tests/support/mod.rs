@@ -0,0 +1,80 @@
+//! Helpers for the integration tests that run the `wordle` binary
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+
+/// The `wordle` binary called with `args`, printing the English messages
+pub fn wordle(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_wordle"));
+    command.args(args).env("LANG", "C");
+    command
+}
+
+/// Start `command` with its output captured, feeding it `input` and closing its standard input
+pub fn spawn(command: &mut Command, input: &str) -> Child {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute process");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child
+}
+
+/// Run `wordle` with `args` until it exits, feeding it `input`
+pub fn run(args: &[&str], input: &str) -> Output {
+    spawn(&mut wordle(args), input).wait_with_output().unwrap()
+}
+
+/// The standard output of a successful run
+pub fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// The standard error of a failed run
+pub fn stderr(output: Output) -> String {
+    assert!(!output.status.success(), "{output:?}");
+    String::from_utf8(output.stderr).unwrap()
+}
+
+/// A directory for the files of one test, removed when the test ends
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("wordle_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Write `content` to the file `name` of the directory, returning its path
+    pub fn write(&self, name: &str, content: &str) -> PathBuf {
+        let path = self.0.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// The parsed JSON content of the file at `path`
+pub fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
@@ -0,0 +1,95 @@
+pub mod support;
+
+use support::{TempDir, run, stderr, stdout};
+
+/// A bot that always guesses CRANE
+const CRANE_BOT: &str =
+    r#"bot:while read line; do case "$line" in END) exit 0 ;; *) echo crane ;; esac; done"#;
+
+/// The columns of each row of the leaderboard after `heading`: rank, mean, failures and strategy
+fn leaderboard<'a>(output: &'a str, heading: &str) -> Vec<Vec<&'a str>> {
+    output
+        .lines()
+        .skip_while(|line| *line != heading)
+        .skip(2)
+        .take_while(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|line| line.split_whitespace().collect())
+        .collect()
+}
+
+#[test]
+fn test_tournament_leaderboard_and_export() {
+    let dir = TempDir::new("tournament");
+    let export = dir.path().join("games.csv");
+    let output = stdout(run(
+        &[
+            "tournament",
+            "--rounds",
+            "3",
+            "--strategy",
+            "frequency",
+            "--strategy",
+            CRANE_BOT,
+            "--export",
+            export.to_str().unwrap(),
+        ],
+        "",
+    ));
+    // CRANE is not among the first answers, so the bot fails every game
+    for heading in ["normal mode, 3 answers", "difficult mode, 3 answers"] {
+        let rows = leaderboard(&output, heading);
+        assert_eq!(rows.len(), 2, "{output}");
+        assert_eq!(rows[1][..3], ["2", "0.00", "3"], "{output}");
+        assert!(rows[1][3].starts_with("bot:"), "{output}");
+    }
+
+    let csv = std::fs::read_to_string(&export).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "mode,strategy,answer,win,attempts,guesses");
+    // 2 strategies, 2 modes and 3 answers
+    assert_eq!(lines.len(), 1 + 2 * 2 * 3);
+    assert!(lines[1].starts_with("normal,frequency,"));
+}
+
+#[test]
+fn test_tournament_unknown_strategy() {
+    let error = stderr(run(&["tournament", "--strategy", "oracle"], ""));
+    assert!(error.contains("Unknown strategy oracle"));
+}
+
+#[test]
+fn test_tournament_duplicate_strategy() {
+    let error = stderr(run(
+        &["tournament", "--strategy", "first", "--strategy", "first"],
+        "",
+    ));
+    assert!(error.contains("Strategy first is given more than once"));
+}
+
+#[test]
+fn test_tournament_head_to_head_words() {
+    let dir = TempDir::new("tournament_splits");
+    let export = dir.path().join("tournament.json");
+    stdout(run(
+        &[
+            "tournament",
+            "--rounds",
+            "3",
+            "--strategy",
+            "frequency",
+            "--strategy",
+            CRANE_BOT,
+            "--export",
+            export.to_str().unwrap(),
+        ],
+        "",
+    ));
+    let export: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+    // the bot fails every game and the other strategy wins every one
+    let splits = export["head_to_head"].as_array().unwrap();
+    assert_eq!(splits.len(), 2 * 3);
+    assert_eq!(splits[0]["strategies"][0], "frequency");
+    assert_eq!(splits[0]["scores"][1], 7);
+    assert_eq!(splits[0]["answer"], export["games"][0]["answer"]);
+}
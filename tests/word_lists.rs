@@ -0,0 +1,59 @@
+pub mod support;
+
+use ntest::timeout;
+use support::{TempDir, run, stderr, stdout};
+
+/// The `n`th five letter word in alphabetical order
+fn nth_word(mut n: usize) -> String {
+    let mut word = [b'a'; 5];
+    for letter in word.iter_mut().rev() {
+        *letter = b'a' + (n % 26) as u8;
+        n /= 26;
+    }
+    String::from_utf8(word.to_vec()).unwrap()
+}
+
+#[test]
+#[timeout(20000)]
+fn test_large_acceptable_list() {
+    let dir = TempDir::new("lists");
+    let words: Vec<String> = (0..300_000).map(nth_word).collect();
+    let acceptable = dir.write("acceptable.txt", &words.join("\n"));
+    let final_set = dir.write("final.txt", &format!("{}\n{}\n", words[0], words[299_999]));
+
+    // many invalid and valid guesses, each looked up in the whole list
+    let mut input = "zzzzz\n".repeat(1000);
+    input += &format!("{}\n{}\n", words[150_000], words[299_999]);
+    let output = stdout(run(
+        &[
+            "-a",
+            acceptable.to_str().unwrap(),
+            "-f",
+            final_set.to_str().unwrap(),
+            "-w",
+            &words[299_999],
+        ],
+        &input,
+    ));
+    assert_eq!(output.matches("INVALID").count(), 1000);
+    assert!(output.ends_with("CORRECT 2\n"), "{output}");
+}
+
+#[test]
+fn test_invalid_word_in_list() {
+    let dir = TempDir::new("invalid_list");
+    let path = dir.write("final.txt", "crane\ncranes\n");
+    let error = stderr(run(&["-f", path.to_str().unwrap()], ""));
+    assert!(error.contains("Invalid word cranes"), "{error}");
+}
+
+#[test]
+fn test_list_in_uppercase() {
+    let dir = TempDir::new("uppercase_list");
+    let final_set = dir.write("final.txt", "CRANE\n\n  Grand \n");
+    let output = stdout(run(
+        &["-f", final_set.to_str().unwrap(), "-w", "grand"],
+        "crane\ngrand\n",
+    ));
+    assert!(output.ends_with("CORRECT 2\n"), "{output}");
+}